@@ -0,0 +1,117 @@
+use crate::{Decimal, RegExp, Value};
+use rslint_parser::{
+    ast::{BinOp, UnaryOp},
+    TextRange,
+};
+use std::sync::Arc;
+
+/// An owned expression node lowered from the rslint syntax tree.
+///
+/// Unlike `SyntaxNode`, this tree holds no reference-counted parser state, so it can
+/// be cloned freely and shared across threads once compiled.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Expr {
+    pub(crate) kind: ExprKind,
+    /// Byte range of the expression in the original source.
+    pub(crate) range: TextRange,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ExprKind {
+    Literal(Value),
+    /// A fractional number literal such as `0.1`. `decimal` is its exact value, used
+    /// instead of the nearest `f64` in `value` when decimal arithmetic is enabled.
+    DecimalLiteral {
+        value: Value,
+        decimal: Decimal,
+    },
+    Identifier(String),
+    Unary {
+        op: UnaryOp,
+        expr: Box<Expr>,
+    },
+    Binary {
+        op: BinOp,
+        lhs: Box<Expr>,
+        rhs: Box<Expr>,
+    },
+    Conditional {
+        test: Box<Expr>,
+        cons: Box<Expr>,
+        alt: Box<Expr>,
+    },
+    /// `object.property` or `object[expr]`.
+    Member {
+        object: Box<Expr>,
+        property: PropertyKey,
+        /// `object?.property`, which short-circuits the rest of the chain when
+        /// `object` is nullish.
+        optional: bool,
+    },
+    Call {
+        callee: Box<Expr>,
+        args: Vec<Expr>,
+        /// `callee?.(args)`.
+        optional: bool,
+    },
+    /// A parenthesised optional chain such as `(a?.b)`. Short-circuiting stops at the
+    /// parentheses, so `(a?.b).c` still fails when `a` is nullish.
+    Chain(Box<Expr>),
+    /// `` `a${x}b` ``, or `` tag`a${x}b` `` when `tag` names a custom function.
+    ///
+    /// `quasis` holds the cooked string parts and always has one more entry than
+    /// `exprs`, since the parts surround every substitution.
+    Template {
+        tag: Option<String>,
+        quasis: Vec<String>,
+        exprs: Vec<Expr>,
+    },
+    /// An expression-bodied arrow function such as `(a, b) => a + b`. The parameters
+    /// and body are shared with the [`Function`](crate::Function) values it evaluates to.
+    Arrow {
+        params: Arc<[String]>,
+        body: Arc<Expr>,
+    },
+    Array(Vec<ArrayElement>),
+    Object(Vec<ObjectProperty>),
+    /// A `/pattern/flags` literal, compiled once when the expression is lowered.
+    RegExp(RegExp),
+    /// `new callee(args)`. Only `new RegExp(...)` can be evaluated.
+    New {
+        callee: Box<Expr>,
+        args: Vec<Expr>,
+    },
+}
+
+/// A single slot in an array literal.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ArrayElement {
+    Expr(Expr),
+    /// `...expr`, expanded into the surrounding array.
+    Spread(Expr),
+    /// An elided slot such as the middle of `[1, , 2]`.
+    Hole,
+}
+
+/// A single entry in an object literal.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ObjectProperty {
+    /// `key: value`, including the shorthand `key` form.
+    KeyValue { key: PropertyKey, value: Expr },
+    /// `...expr`, copying the entries of `expr` into the surrounding object.
+    Spread(Expr),
+}
+
+/// A property name in an object literal or member access.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum PropertyKey {
+    Static(String),
+    /// `[expr]`, where the key is evaluated and coerced to a string.
+    Computed(Box<Expr>),
+}
+
+impl Expr {
+    pub(crate) fn new(kind: ExprKind, range: TextRange) -> Self {
+        Expr { kind, range }
+    }
+}
@@ -0,0 +1,812 @@
+use crate::ast::{ArrayElement, Expr, ExprKind, ObjectProperty, PropertyKey};
+use crate::interpreter::Interpreter;
+use crate::location::full_range;
+use crate::regexp::RegExpCache;
+use crate::value::parse_decimal;
+use crate::{
+    BigInt, Context, EvaluationError, Evaluator, Location, NodeError, Number, ParseDiagnostic,
+    RegExp, Value,
+};
+use num_traits::ToPrimitive;
+use rslint_parser::{
+    ast::{
+        ArrowExpr, BinExpr, BinOp, BracketExpr, CallExpr, ComputedPropertyName, CondExpr, DotExpr,
+        Expr as AstExpr, ExprOrBlock, GroupingExpr, IdentProp, LiteralProp, Name, NameRef, NewExpr,
+        SpreadElement, SpreadProp, TemplateElement, UnaryExpr,
+    },
+    parse_expr, AstNode, NodeOrToken, ParserError, SyntaxKind, SyntaxNode, SyntaxNodeExt,
+    TextRange, TextSize,
+};
+use std::sync::Arc;
+use tracing::trace;
+
+/// An expression that has been parsed and lowered once, ready to be evaluated any
+/// number of times without going back through the parser.
+///
+/// Compiled expressions own their tree and are `Send + Sync + Clone`, so a rule set
+/// can be compiled up front and shared between threads.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompiledExpression {
+    source: Arc<str>,
+    root: Expr,
+    /// Patterns compiled by `new RegExp(...)` during evaluation. Regex literals are
+    /// compiled up front and live in `root`.
+    regexps: Arc<RegExpCache>,
+}
+
+impl CompiledExpression {
+    pub(crate) fn compile(expression: &str) -> Result<Self, EvaluationError> {
+        if expression.trim().is_empty() {
+            return Err(EvaluationError::Node(NodeError {
+                message: "Empty expression".to_string(),
+                range: None,
+                location: None,
+            })
+            .locate(full_range(expression), expression));
+        }
+
+        // Parse in expression position so that a leading `{` is an object literal
+        // rather than a block statement.
+        let parse = parse_expr(expression, 0);
+        let untyped_expr_node = parse.syntax();
+
+        let mut diagnostics: Vec<_> = parse
+            .errors()
+            .iter()
+            .map(|error| parse_diagnostic(error, expression))
+            .collect();
+        // The parser stops after one expression, so `a; b` would otherwise evaluate `a`
+        let end = usize::from(untyped_expr_node.text_range().end());
+        let rest = &expression[end..];
+        if !rest.trim().is_empty() {
+            let start = end + rest.len() - rest.trim_start().len();
+            diagnostics.push(ParseDiagnostic {
+                message: "Unexpected input after the end of the expression".to_string(),
+                location: Location::new(text_range(start..expression.trim_end().len()), expression),
+            });
+        }
+        if !diagnostics.is_empty() {
+            return Err(EvaluationError::ParseError(diagnostics));
+        }
+
+        trace!(
+            "Expression AST:\n\n{:#?}\n-----------------",
+            untyped_expr_node
+        );
+
+        let root = lower_node(&untyped_expr_node)
+            .map_err(|err| err.locate(untyped_expr_node.trimmed_range(), expression))?;
+
+        Ok(CompiledExpression {
+            source: Arc::from(expression),
+            root,
+            regexps: Arc::default(),
+        })
+    }
+
+    /// The source text this expression was compiled from.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Evaluates the expression using the context and custom functions of `evaluator`.
+    pub fn evaluate(&self, evaluator: &Evaluator) -> Result<serde_json::Value, EvaluationError> {
+        self.evaluate_with(evaluator, &evaluator.context)
+    }
+
+    /// Evaluates the expression using the custom functions of `evaluator` and a
+    /// borrowed per-call `context`.
+    pub fn evaluate_with(
+        &self,
+        evaluator: &Evaluator,
+        context: &dyn Context,
+    ) -> Result<serde_json::Value, EvaluationError> {
+        self.evaluate_value_with(evaluator, context)?
+            .to_json(evaluator.non_json_policy)
+            .map_err(|err| err.locate(self.root.range, &self.source))
+    }
+
+    /// Like [`CompiledExpression::evaluate`], but returns the result as a [`Value`]
+    /// without converting it to JSON.
+    pub fn evaluate_value(&self, evaluator: &Evaluator) -> Result<Value, EvaluationError> {
+        self.evaluate_value_with(evaluator, &evaluator.context)
+    }
+
+    /// Like [`CompiledExpression::evaluate_with`], but returns the result as a [`Value`].
+    pub fn evaluate_value_with(
+        &self,
+        evaluator: &Evaluator,
+        context: &dyn Context,
+    ) -> Result<Value, EvaluationError> {
+        let interpreter = Interpreter::new(evaluator, context, &self.source, &self.regexps);
+        let result = interpreter.round_result(interpreter.evaluate_node(&self.root)?);
+
+        trace!("Result: {:?}", result);
+
+        Ok(result)
+    }
+}
+
+fn parse_diagnostic(error: &ParserError, source: &str) -> ParseDiagnostic {
+    // Diagnostics point at the offending token, or failing that at related code such
+    // as the start of an unterminated string
+    let span = error
+        .primary
+        .as_ref()
+        .or_else(|| error.children.first())
+        .map(|sub| sub.span.range.clone())
+        .unwrap_or_default();
+    ParseDiagnostic {
+        message: error.title.clone(),
+        location: Location::new(text_range(span), source),
+    }
+}
+
+fn text_range(range: std::ops::Range<usize>) -> TextRange {
+    TextRange::new(
+        TextSize::from(range.start as u32),
+        TextSize::from(range.end as u32),
+    )
+}
+
+fn lower_node(node: &SyntaxNode) -> Result<Expr, EvaluationError> {
+    trace!(
+        "Lowering NodeKind: {:#?}, {:?}",
+        node.kind(),
+        node.to_string()
+    );
+
+    let kind = match node.kind() {
+        SyntaxKind::GROUPING_EXPR => {
+            let grouping_expr = GroupingExpr::cast(node.clone()).unwrap();
+            let inner_expr = grouping_expr.inner().ok_or_else(|| {
+                EvaluationError::Node(NodeError {
+                    message: "Missing inner expression in grouping expression".to_string(),
+                    range: Some(node.text_range()),
+                    location: None,
+                })
+            })?;
+            let inner = lower_node(inner_expr.syntax())?;
+            if !is_optional_chain(&inner) {
+                return Ok(inner);
+            }
+            ExprKind::Chain(Box::new(inner))
+        }
+        SyntaxKind::DOT_EXPR => lower_dot_expr(&DotExpr::cast(node.clone()).unwrap())?,
+        SyntaxKind::BRACKET_EXPR => lower_bracket_expr(&BracketExpr::cast(node.clone()).unwrap())?,
+        SyntaxKind::NAME_REF => lower_name_ref(&NameRef::cast(node.clone()).unwrap())?,
+        SyntaxKind::NAME => lower_name(&Name::cast(node.clone()).unwrap())?,
+        SyntaxKind::BIN_EXPR => lower_bin_expr(&BinExpr::cast(node.clone()).unwrap())?,
+        SyntaxKind::LITERAL
+            if node.first_token().map(|token| token.kind()) == Some(SyntaxKind::REGEX) =>
+        {
+            lower_regex_literal(node)?
+        }
+        SyntaxKind::LITERAL => lower_literal_expr(node)?,
+        SyntaxKind::COND_EXPR => lower_cond_expr(&CondExpr::cast(node.clone()).unwrap())?,
+        SyntaxKind::UNARY_EXPR => lower_prefix_expr(&UnaryExpr::cast(node.clone()).unwrap())?,
+        SyntaxKind::CALL_EXPR => lower_call_expr(&CallExpr::cast(node.clone()).unwrap())?,
+        SyntaxKind::NEW_EXPR => lower_new_expr(&NewExpr::cast(node.clone()).unwrap())?,
+        SyntaxKind::TEMPLATE => lower_template(node)?,
+        SyntaxKind::ARROW_EXPR => lower_arrow_expr(&ArrowExpr::cast(node.clone()).unwrap())?,
+        SyntaxKind::ARRAY_EXPR => lower_array_expr(node)?,
+        SyntaxKind::OBJECT_EXPR => lower_object_expr(node)?,
+        _ => {
+            return Err(EvaluationError::Node(NodeError {
+                message: format!("Unsupported syntax kind: {:?}", node.kind()),
+                range: Some(node.text_range()),
+                location: None,
+            }))
+        }
+    };
+
+    // Leave out surrounding whitespace and comments, so error locations underline
+    // just the code
+    Ok(Expr::new(kind, node.trimmed_range()))
+}
+
+fn lower_bin_expr(bin_expr: &BinExpr) -> Result<ExprKind, EvaluationError> {
+    let left = bin_expr.lhs().ok_or_else(|| NodeError {
+        message: "[Empty BinExpr Left Expression]".to_string(),
+        range: Some(bin_expr.syntax().text_range()),
+        location: None,
+    })?;
+    let right = bin_expr.rhs().ok_or_else(|| NodeError {
+        message: "[Empty BinExpr Right Expression]".to_string(),
+        range: Some(bin_expr.syntax().text_range()),
+        location: None,
+    })?;
+    let (op_token, op) = bin_expr.op_details().ok_or_else(|| NodeError {
+        message: "Unsupported binary operator".to_string(),
+        range: Some(bin_expr.syntax().text_range()),
+        location: None,
+    })?;
+
+    // JavaScript rejects `a ?? b || c` and `a && b ?? c`; the operands have to be
+    // parenthesised to make the intended grouping explicit.
+    for operand in [&left, &right] {
+        let operand_op = match operand {
+            AstExpr::BinExpr(inner) => inner.op_details(),
+            _ => None,
+        };
+        if let Some((operand_token, operand_op)) = operand_op {
+            if mixes_nullish_with_logical(op, operand_op) {
+                return Err(EvaluationError::Node(NodeError {
+                    message: format!(
+                        "Cannot mix '{}' with '{}' without parentheses",
+                        operand_token.text(),
+                        op_token.text()
+                    ),
+                    range: Some(op_token.text_range()),
+                    location: None,
+                }));
+            }
+        }
+    }
+
+    Ok(ExprKind::Binary {
+        op,
+        lhs: Box::new(lower_node(left.syntax())?),
+        rhs: Box::new(lower_node(right.syntax())?),
+    })
+}
+
+fn mixes_nullish_with_logical(a: BinOp, b: BinOp) -> bool {
+    let is_logical = |op| matches!(op, BinOp::LogicalAnd | BinOp::LogicalOr);
+    (a == BinOp::NullishCoalescing && is_logical(b))
+        || (b == BinOp::NullishCoalescing && is_logical(a))
+}
+
+fn lower_prefix_expr(prefix_expr: &UnaryExpr) -> Result<ExprKind, EvaluationError> {
+    let expr = prefix_expr.expr().ok_or_else(|| NodeError {
+        message: "[Empty PrefixExpr Expression]".to_string(),
+        range: Some(prefix_expr.syntax().text_range()),
+        location: None,
+    })?;
+    let (_, op) = prefix_expr.op_details().ok_or_else(|| NodeError {
+        message: "Unsupported unary operator".to_string(),
+        range: Some(prefix_expr.syntax().text_range()),
+        location: None,
+    })?;
+
+    Ok(ExprKind::Unary {
+        op,
+        expr: Box::new(lower_node(expr.syntax())?),
+    })
+}
+
+fn lower_cond_expr(cond_expr: &CondExpr) -> Result<ExprKind, EvaluationError> {
+    let cond = cond_expr.test().ok_or_else(|| NodeError {
+        message: "[Empty CondExpr Test Expression]".to_string(),
+        range: Some(cond_expr.syntax().text_range()),
+        location: None,
+    })?;
+    let true_expr = cond_expr.cons().ok_or_else(|| NodeError {
+        message: "[Empty CondExpr Consequent Expression]".to_string(),
+        range: Some(cond_expr.syntax().text_range()),
+        location: None,
+    })?;
+    let false_expr = cond_expr.alt().ok_or_else(|| NodeError {
+        message: "[Empty CondExpr Alternate Expression]".to_string(),
+        range: Some(cond_expr.syntax().text_range()),
+        location: None,
+    })?;
+
+    Ok(ExprKind::Conditional {
+        test: Box::new(lower_node(cond.syntax())?),
+        cons: Box::new(lower_node(true_expr.syntax())?),
+        alt: Box::new(lower_node(false_expr.syntax())?),
+    })
+}
+
+fn lower_dot_expr(dot_expr: &DotExpr) -> Result<ExprKind, EvaluationError> {
+    let object_expr = dot_expr.object().ok_or_else(|| {
+        EvaluationError::Node(NodeError {
+            message: "Missing object in dot expression".to_string(),
+            range: Some(dot_expr.syntax().text_range()),
+            location: None,
+        })
+    })?;
+
+    let prop_name_ident = dot_expr.prop().ok_or_else(|| {
+        EvaluationError::Node(NodeError {
+            message: "Missing property name in dot expression".to_string(),
+            range: Some(dot_expr.syntax().text_range()),
+            location: None,
+        })
+    })?;
+    // In rslint_parser, prop for DotExpr is an Name rather than NameRef or Ident
+    // So we need to get its text representation.
+    let property = prop_name_ident.syntax().text().to_string();
+
+    Ok(ExprKind::Member {
+        object: Box::new(lower_node(object_expr.syntax())?),
+        property: PropertyKey::Static(property),
+        optional: dot_expr.opt_chain_token().is_some(),
+    })
+}
+
+fn lower_bracket_expr(bracket_expr: &BracketExpr) -> Result<ExprKind, EvaluationError> {
+    let object_expr = bracket_expr.object().ok_or_else(|| NodeError {
+        message: "Missing object in bracket expression".to_string(),
+        range: Some(bracket_expr.syntax().text_range()),
+        location: None,
+    })?;
+    let prop_expr = bracket_expr.prop().ok_or_else(|| NodeError {
+        message: "Missing property in bracket expression".to_string(),
+        range: Some(bracket_expr.syntax().text_range()),
+        location: None,
+    })?;
+
+    Ok(ExprKind::Member {
+        object: Box::new(lower_node(object_expr.syntax())?),
+        property: PropertyKey::Computed(Box::new(lower_node(prop_expr.syntax())?)),
+        optional: bracket_expr.opt_chain_token().is_some(),
+    })
+}
+
+fn lower_arrow_expr(arrow_expr: &ArrowExpr) -> Result<ExprKind, EvaluationError> {
+    let node = arrow_expr.syntax();
+    if arrow_expr.async_token().is_some() {
+        return Err(EvaluationError::Node(NodeError {
+            message: "Async arrow functions are not supported".to_string(),
+            range: Some(node.text_range()),
+            location: None,
+        }));
+    }
+
+    // `x => ...` has a bare NAME, `(a, b) => ...` a PARAMETER_LIST of patterns
+    let mut params = Vec::new();
+    match node.first_child() {
+        Some(param) if param.kind() == SyntaxKind::NAME => params.push(param.text().to_string()),
+        Some(list) if list.kind() == SyntaxKind::PARAMETER_LIST => {
+            for pattern in list.children() {
+                match pattern.first_child() {
+                    Some(name)
+                        if pattern.kind() == SyntaxKind::SINGLE_PATTERN
+                            && name.kind() == SyntaxKind::NAME =>
+                    {
+                        params.push(name.text().to_string())
+                    }
+                    _ => {
+                        return Err(EvaluationError::Node(NodeError {
+                            message: format!(
+                                "Unsupported arrow function parameter: {:?}. Only plain identifiers are supported.",
+                                pattern.kind()
+                            ),
+                            range: Some(pattern.text_range()),
+                            location: None,
+                        }))
+                    }
+                }
+            }
+        }
+        _ => {
+            return Err(EvaluationError::Node(NodeError {
+                message: "Missing parameters in arrow function".to_string(),
+                range: Some(node.text_range()),
+                location: None,
+            }))
+        }
+    }
+
+    let body =
+        match arrow_expr.body() {
+            Some(ExprOrBlock::Expr(body)) => lower_node(body.syntax())?,
+            Some(ExprOrBlock::Block(block)) => return Err(EvaluationError::Node(NodeError {
+                message:
+                    "Arrow functions with block bodies are not supported; use an expression body"
+                        .to_string(),
+                range: Some(block.syntax().text_range()),
+                location: None,
+            })),
+            None => {
+                return Err(EvaluationError::Node(NodeError {
+                    message: "Missing body in arrow function".to_string(),
+                    range: Some(node.text_range()),
+                    location: None,
+                }))
+            }
+        };
+
+    Ok(ExprKind::Arrow {
+        params: params.into(),
+        body: Arc::new(body),
+    })
+}
+
+fn lower_template(node: &SyntaxNode) -> Result<ExprKind, EvaluationError> {
+    let mut tag = None;
+    let mut quasis = vec![String::new()];
+    let mut exprs = Vec::new();
+    let mut in_template = false;
+
+    for child in node.children_with_tokens() {
+        match child {
+            NodeOrToken::Token(token) if token.kind() == SyntaxKind::BACKTICK => {
+                in_template = true;
+            }
+            NodeOrToken::Token(token) if token.kind() == SyntaxKind::TEMPLATE_CHUNK => {
+                quasis
+                    .last_mut()
+                    .unwrap()
+                    .push_str(&process_escape_sequences(token.text()));
+            }
+            NodeOrToken::Node(child) if child.kind() == SyntaxKind::TEMPLATE_ELEMENT => {
+                let inner = TemplateElement::cast(child.clone())
+                    .unwrap()
+                    .expr()
+                    .ok_or_else(|| NodeError {
+                        message: "Missing expression in template substitution".to_string(),
+                        range: Some(child.text_range()),
+                        location: None,
+                    })?;
+                exprs.push(lower_node(inner.syntax())?);
+                quasis.push(String::new());
+            }
+            // Anything before the opening backtick is the tag
+            NodeOrToken::Node(child) if !in_template => {
+                if child.kind() != SyntaxKind::NAME_REF {
+                    return Err(EvaluationError::Node(NodeError {
+                        message: format!(
+                            "Unsupported template tag: {:?}. Expected the name of a custom function.",
+                            child.kind()
+                        ),
+                        range: Some(child.text_range()),
+                        location: None,
+                    }));
+                }
+                tag = Some(child.text().to_string());
+            }
+            _ => {}
+        }
+    }
+
+    Ok(ExprKind::Template { tag, quasis, exprs })
+}
+
+fn lower_array_expr(node: &SyntaxNode) -> Result<ExprKind, EvaluationError> {
+    let mut elements = Vec::new();
+    // A comma that is not preceded by an element since the previous comma (or the
+    // opening bracket) marks a hole, e.g. `[1, , 2]` or `[,]`.
+    let mut seen_element = false;
+
+    for child in node.children_with_tokens() {
+        match child {
+            NodeOrToken::Node(child) => {
+                let element = if child.kind() == SyntaxKind::SPREAD_ELEMENT {
+                    let spread = SpreadElement::cast(child.clone()).unwrap();
+                    let inner = spread.element().ok_or_else(|| NodeError {
+                        message: "Missing expression in spread element".to_string(),
+                        range: Some(child.text_range()),
+                        location: None,
+                    })?;
+                    ArrayElement::Spread(lower_node(inner.syntax())?)
+                } else {
+                    ArrayElement::Expr(lower_node(&child)?)
+                };
+                elements.push(element);
+                seen_element = true;
+            }
+            NodeOrToken::Token(token) if token.kind() == SyntaxKind::COMMA => {
+                if !seen_element {
+                    elements.push(ArrayElement::Hole);
+                }
+                seen_element = false;
+            }
+            NodeOrToken::Token(_) => {}
+        }
+    }
+
+    Ok(ExprKind::Array(elements))
+}
+
+fn lower_object_expr(node: &SyntaxNode) -> Result<ExprKind, EvaluationError> {
+    let mut properties = Vec::new();
+
+    for prop in node.children() {
+        let property = match prop.kind() {
+            SyntaxKind::LITERAL_PROP => {
+                let literal_prop = LiteralProp::cast(prop.clone()).unwrap();
+                let key_node = prop.first_child().ok_or_else(|| NodeError {
+                    message: "Missing key in object property".to_string(),
+                    range: Some(prop.text_range()),
+                    location: None,
+                })?;
+                let value = literal_prop.value().ok_or_else(|| NodeError {
+                    message: "Missing value in object property".to_string(),
+                    range: Some(prop.text_range()),
+                    location: None,
+                })?;
+                ObjectProperty::KeyValue {
+                    key: lower_property_key(&key_node)?,
+                    value: lower_node(value.syntax())?,
+                }
+            }
+            SyntaxKind::IDENT_PROP => {
+                // Shorthand `{ short }` is sugar for `{ short: short }`
+                let name = IdentProp::cast(prop.clone())
+                    .unwrap()
+                    .name()
+                    .ok_or_else(|| NodeError {
+                        message: "Missing name in shorthand property".to_string(),
+                        range: Some(prop.text_range()),
+                        location: None,
+                    })?;
+                let key = name.syntax().text().to_string();
+                ObjectProperty::KeyValue {
+                    value: Expr::new(ExprKind::Identifier(key.clone()), prop.text_range()),
+                    key: PropertyKey::Static(key),
+                }
+            }
+            SyntaxKind::SPREAD_PROP => {
+                let value = SpreadProp::cast(prop.clone())
+                    .unwrap()
+                    .value()
+                    .ok_or_else(|| NodeError {
+                        message: "Missing expression in spread property".to_string(),
+                        range: Some(prop.text_range()),
+                        location: None,
+                    })?;
+                ObjectProperty::Spread(lower_node(value.syntax())?)
+            }
+            kind => {
+                return Err(EvaluationError::Node(NodeError {
+                    message: format!("Unsupported object property kind: {:?}", kind),
+                    range: Some(prop.text_range()),
+                    location: None,
+                }))
+            }
+        };
+        properties.push(property);
+    }
+
+    Ok(ExprKind::Object(properties))
+}
+
+fn lower_property_key(key_node: &SyntaxNode) -> Result<PropertyKey, EvaluationError> {
+    match key_node.kind() {
+        SyntaxKind::NAME => Ok(PropertyKey::Static(key_node.text().to_string())),
+        SyntaxKind::COMPUTED_PROPERTY_NAME => {
+            let expr = ComputedPropertyName::cast(key_node.clone())
+                .unwrap()
+                .prop()
+                .ok_or_else(|| NodeError {
+                    message: "Missing expression in computed property name".to_string(),
+                    range: Some(key_node.text_range()),
+                    location: None,
+                })?;
+            Ok(PropertyKey::Computed(Box::new(lower_node(expr.syntax())?)))
+        }
+        // String and numeric keys are fixed at compile time, e.g. `{ 'b-c': 1, 2: 3 }`
+        SyntaxKind::LITERAL => match lower_literal(key_node)? {
+            Value::String(key) => Ok(PropertyKey::Static(key)),
+            Value::Number(n) => Ok(PropertyKey::Static(n.to_string())),
+            Value::BigInt(b) => Ok(PropertyKey::Static(b.to_string())),
+            Value::Bool(b) => Ok(PropertyKey::Static(b.to_string())),
+            _ => Ok(PropertyKey::Static("null".to_string())),
+        },
+        kind => Err(EvaluationError::Node(NodeError {
+            message: format!("Unsupported property key kind: {:?}", kind),
+            range: Some(key_node.text_range()),
+            location: None,
+        })),
+    }
+}
+
+fn lower_call_expr(call_expr: &CallExpr) -> Result<ExprKind, EvaluationError> {
+    let callee_expr_node = call_expr.callee().ok_or_else(|| {
+        EvaluationError::Node(NodeError {
+            message: "Missing callee in call expression".to_string(),
+            range: Some(call_expr.syntax().text_range()),
+            location: None,
+        })
+    })?;
+
+    let mut args = Vec::new();
+    if let Some(arg_list_node) = call_expr.arguments() {
+        for arg_expr in arg_list_node.args() {
+            args.push(lower_node(arg_expr.syntax())?);
+        }
+    }
+
+    Ok(ExprKind::Call {
+        callee: Box::new(lower_node(callee_expr_node.syntax())?),
+        args,
+        optional: call_expr.opt_chain_token().is_some(),
+    })
+}
+
+fn lower_new_expr(new_expr: &NewExpr) -> Result<ExprKind, EvaluationError> {
+    let callee = new_expr.object().ok_or_else(|| NodeError {
+        message: "Missing constructor in new expression".to_string(),
+        range: Some(new_expr.syntax().text_range()),
+        location: None,
+    })?;
+
+    // `new RegExp` without parentheses is a call with no arguments
+    let mut args = Vec::new();
+    if let Some(arg_list_node) = new_expr.arguments() {
+        for arg_expr in arg_list_node.args() {
+            args.push(lower_node(arg_expr.syntax())?);
+        }
+    }
+
+    Ok(ExprKind::New {
+        callee: Box::new(lower_node(callee.syntax())?),
+        args,
+    })
+}
+
+/// Compiles a `/pattern/flags` literal, so an invalid pattern is reported before the
+/// expression is ever evaluated.
+fn lower_regex_literal(literal: &SyntaxNode) -> Result<ExprKind, NodeError> {
+    let text = literal.to_string();
+    let (source, flags) = text[1..].rsplit_once('/').unwrap_or((&text[1..], ""));
+    let regexp = RegExp::new(source, flags).map_err(|reason| NodeError {
+        message: format!("Invalid regular expression: {}: {}", text, reason),
+        range: Some(literal.text_range()),
+        location: None,
+    })?;
+    Ok(ExprKind::RegExp(regexp))
+}
+
+/// Whether `expr` is a member/call chain containing at least one `?.` link.
+fn is_optional_chain(expr: &Expr) -> bool {
+    match &expr.kind {
+        ExprKind::Member {
+            object, optional, ..
+        } => *optional || is_optional_chain(object),
+        ExprKind::Call {
+            callee, optional, ..
+        } => *optional || is_optional_chain(callee),
+        _ => false,
+    }
+}
+
+fn lower_name(name: &Name) -> Result<ExprKind, NodeError> {
+    let identifier_name = name
+        .ident_token()
+        .ok_or_else(|| NodeError {
+            message: "[Empty Name]".to_string(),
+            range: Some(name.syntax().text_range()),
+            location: None,
+        })?
+        .to_string();
+
+    Ok(ExprKind::Identifier(identifier_name))
+}
+
+fn lower_name_ref(name_ref: &NameRef) -> Result<ExprKind, NodeError> {
+    let identifier_name = name_ref
+        .ident_token()
+        .ok_or_else(|| NodeError {
+            message: "[Empty NameRef]".to_string(),
+            range: Some(name_ref.syntax().text_range()),
+            location: None,
+        })?
+        .to_string();
+
+    Ok(ExprKind::Identifier(identifier_name))
+}
+
+fn lower_literal_expr(literal: &SyntaxNode) -> Result<ExprKind, NodeError> {
+    let value = lower_literal(literal)?;
+    if let Value::Number(n) = &value {
+        // Integers are already exact, and `0x`-style literals cannot have a fraction
+        if n.is_f64() {
+            if let Some(decimal) = parse_decimal(&literal.to_string().replace('_', "")) {
+                return Ok(ExprKind::DecimalLiteral { value, decimal });
+            }
+        }
+    }
+    Ok(ExprKind::Literal(value))
+}
+
+fn lower_literal(literal: &SyntaxNode) -> Result<Value, NodeError> {
+    let literal_str = literal.to_string();
+
+    // Handle numeric literals
+    if literal.first_token().map(|token| token.kind()) == Some(SyntaxKind::NUMBER) {
+        return lower_number_literal(&literal_str).ok_or_else(|| NodeError {
+            message: format!("Invalid number literal: {}", literal_str),
+            range: Some(literal.text_range()),
+            location: None,
+        });
+    }
+
+    // Handle string literals with escape sequences
+    if literal_str.starts_with('"') || literal_str.starts_with('\'') {
+        // Remove only the first and last character (the quotes)
+        let unquoted = if literal_str.len() >= 2 {
+            &literal_str[1..literal_str.len() - 1]
+        } else {
+            ""
+        };
+        // Process escape sequences
+        let processed = process_escape_sequences(unquoted);
+        return Ok(Value::String(processed));
+    }
+
+    // Handle boolean literals
+    match literal_str.as_str() {
+        "true" => return Ok(Value::Bool(true)),
+        "false" => return Ok(Value::Bool(false)),
+        "null" => return Ok(Value::Null),
+        _ => {}
+    }
+
+    Err(NodeError {
+        message: format!("Unknown literal type: {}", literal_str),
+        range: Some(literal.text_range()),
+        location: None,
+    })
+}
+
+/// Reads a number or BigInt literal. Integers are read exactly, so 64-bit IDs such as
+/// `9007199254740993` keep their value.
+fn lower_number_literal(text: &str) -> Option<Value> {
+    let text = text.replace('_', "");
+    let (text, is_bigint) = match text.strip_suffix('n') {
+        Some(text) => (text, true),
+        None => (text.as_str(), false),
+    };
+    let is_legacy_octal =
+        text.len() > 1 && text.starts_with('0') && text.bytes().all(|b| matches!(b, b'0'..=b'7'));
+    let (radix, digits) = match text.get(..2) {
+        Some("0x" | "0X") => (16, &text[2..]),
+        Some("0o" | "0O") => (8, &text[2..]),
+        Some("0b" | "0B") => (2, &text[2..]),
+        // `017` is 15 in sloppy-mode JavaScript
+        _ if is_legacy_octal => (8, &text[1..]),
+        _ => (10, text),
+    };
+
+    if is_bigint {
+        return BigInt::parse_bytes(digits.as_bytes(), radix).map(Value::BigInt);
+    }
+    if let Ok(integer) = u64::from_str_radix(digits, radix) {
+        return Some(Value::Number(Number::from(integer)));
+    }
+    let number = if radix == 10 {
+        digits.parse::<f64>().ok()?
+    } else {
+        BigInt::parse_bytes(digits.as_bytes(), radix)?.to_f64()?
+    };
+    Some(Value::Number(Number::from_computed(number)))
+}
+
+fn process_escape_sequences(s: &str) -> String {
+    let mut result = String::new();
+    let mut chars = s.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            if let Some(next_ch) = chars.next() {
+                match next_ch {
+                    'n' => result.push('\n'),
+                    't' => result.push('\t'),
+                    'r' => result.push('\r'),
+                    '\\' => result.push('\\'),
+                    '\'' => result.push('\''),
+                    '"' => result.push('"'),
+                    '`' => result.push('`'),
+                    '$' => result.push('$'),
+                    '0' => result.push('\0'),
+                    // For simplicity, we don't handle \uXXXX or \xXX here
+                    // Just pass through the escaped character
+                    _ => {
+                        result.push('\\');
+                        result.push(next_ch);
+                    }
+                }
+            } else {
+                result.push('\\');
+            }
+        } else {
+            result.push(ch);
+        }
+    }
+
+    result
+}
@@ -0,0 +1,103 @@
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasher;
+
+/// Resolves identifiers to values during evaluation.
+///
+/// Implement this for your own types to look variables up lazily (from a struct, a
+/// database row, a cache, ...) instead of materialising every variable into a
+/// `HashMap` up front. Return `Cow::Borrowed` when the value already lives in the
+/// context and `Cow::Owned` when it is computed on demand.
+///
+/// ```
+/// use exprimo::{Context, Evaluator};
+/// use serde_json::Value;
+/// use std::borrow::Cow;
+///
+/// struct Order {
+///     total: f64,
+/// }
+///
+/// impl Context for Order {
+///     fn get(&self, name: &str) -> Option<Cow<'_, Value>> {
+///         match name {
+///             "total" => Some(Cow::Owned(Value::from(self.total))),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let evaluator = Evaluator::default();
+/// let result = evaluator.evaluate_with("total > 100", &Order { total: 250.0 }).unwrap();
+/// assert_eq!(result, Value::Bool(true));
+/// ```
+pub trait Context {
+    /// Returns the value bound to `name`, or `None` if it is not defined.
+    fn get(&self, name: &str) -> Option<Cow<'_, Value>>;
+}
+
+impl<S: BuildHasher> Context for HashMap<String, Value, S> {
+    fn get(&self, name: &str) -> Option<Cow<'_, Value>> {
+        HashMap::get(self, name).map(Cow::Borrowed)
+    }
+}
+
+impl Context for BTreeMap<String, Value> {
+    fn get(&self, name: &str) -> Option<Cow<'_, Value>> {
+        BTreeMap::get(self, name).map(Cow::Borrowed)
+    }
+}
+
+impl Context for serde_json::Map<String, Value> {
+    fn get(&self, name: &str) -> Option<Cow<'_, Value>> {
+        serde_json::Map::get(self, name).map(Cow::Borrowed)
+    }
+}
+
+impl<T: Context + ?Sized> Context for &T {
+    fn get(&self, name: &str) -> Option<Cow<'_, Value>> {
+        (**self).get(name)
+    }
+}
+
+/// A stack of contexts searched in order, so that earlier layers shadow later ones.
+///
+/// ```
+/// use exprimo::{Evaluator, LayeredContext};
+/// use serde_json::{json, Value};
+/// use std::collections::HashMap;
+///
+/// let mut request = HashMap::new();
+/// request.insert("limit".to_string(), json!(5));
+/// let mut defaults = HashMap::new();
+/// defaults.insert("limit".to_string(), json!(10));
+/// defaults.insert("region".to_string(), json!("eu"));
+///
+/// let context = LayeredContext::new().with(&request).with(&defaults);
+/// let evaluator = Evaluator::default();
+/// assert_eq!(evaluator.evaluate_with("limit", &context).unwrap(), json!(5));
+/// assert_eq!(evaluator.evaluate_with("region", &context).unwrap(), json!("eu"));
+/// ```
+#[derive(Default)]
+pub struct LayeredContext<'a> {
+    layers: Vec<&'a dyn Context>,
+}
+
+impl<'a> LayeredContext<'a> {
+    pub fn new() -> Self {
+        LayeredContext { layers: Vec::new() }
+    }
+
+    /// Adds a layer below the existing ones.
+    pub fn with(mut self, layer: &'a dyn Context) -> Self {
+        self.layers.push(layer);
+        self
+    }
+}
+
+impl Context for LayeredContext<'_> {
+    fn get(&self, name: &str) -> Option<Cow<'_, Value>> {
+        self.layers.iter().find_map(|layer| layer.get(name))
+    }
+}
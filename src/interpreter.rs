@@ -0,0 +1,1354 @@
+use crate::ast::{ArrayElement, Expr, ExprKind, ObjectProperty, PropertyKey};
+use crate::regexp::RegExpCache;
+use crate::value::{DecimalMode, MAX_SAFE_INTEGER};
+use crate::{
+    Argument, BuiltInMethodKind, Context, CustomFuncError, CustomFunction, EvaluationError,
+    Evaluator, Function, Lambda, Map, NodeError, NonJsonPolicy, Number, ResolvableValue,
+    UnsafeIntegerPolicy, Value,
+};
+use globals::Namespace;
+use num_traits::Zero;
+use rslint_parser::ast::{BinOp, UnaryOp};
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::trace;
+
+mod array;
+mod bigint;
+mod decimal;
+mod globals;
+mod json;
+mod math;
+mod object;
+mod regexp;
+mod string;
+
+/// Walks a compiled [`Expr`] tree against a context and an evaluator's functions.
+pub(crate) struct Interpreter<'a> {
+    context: &'a dyn Context,
+    /// The parameters of the arrow functions being called, which shadow the context.
+    scope: Option<Arc<Scope>>,
+    custom_functions: &'a HashMap<String, Arc<dyn CustomFunction>>,
+    source: &'a str,
+    regexps: &'a RegExpCache,
+    math_enabled: bool,
+    bigint_enabled: bool,
+    decimal: Option<DecimalMode>,
+    non_json_policy: NonJsonPolicy,
+    unsafe_integer_policy: UnsafeIntegerPolicy,
+}
+
+/// Variables bound by an arrow function call, on top of those in scope where the
+/// function was written.
+pub(crate) struct Scope {
+    names: Arc<[String]>,
+    values: Vec<Value>,
+    parent: Option<Arc<Scope>>,
+}
+
+impl Scope {
+    pub(crate) fn new(
+        names: Arc<[String]>,
+        values: Vec<Value>,
+        parent: Option<Arc<Scope>>,
+    ) -> Self {
+        Scope {
+            names,
+            values,
+            parent,
+        }
+    }
+
+    fn get(&self, name: &str) -> Option<&Value> {
+        match self.names.iter().position(|variable| variable == name) {
+            Some(i) => self.values.get(i),
+            None => self.parent.as_deref()?.get(name),
+        }
+    }
+}
+
+impl<'a> Interpreter<'a> {
+    pub(crate) fn new(
+        evaluator: &'a Evaluator,
+        context: &'a dyn Context,
+        source: &'a str,
+        regexps: &'a RegExpCache,
+    ) -> Self {
+        Interpreter {
+            context,
+            scope: None,
+            custom_functions: &evaluator.custom_functions,
+            source,
+            regexps,
+            math_enabled: evaluator.math_enabled,
+            bigint_enabled: evaluator.bigint_enabled,
+            decimal: evaluator.decimal,
+            non_json_policy: evaluator.non_json_policy,
+            unsafe_integer_policy: evaluator.unsafe_integer_policy,
+        }
+    }
+
+    pub(crate) fn non_json_policy(&self) -> NonJsonPolicy {
+        self.non_json_policy
+    }
+
+    /// Returns an interpreter that resolves identifiers in `scope` before the context,
+    /// used for the body of an arrow function.
+    pub(crate) fn with_scope(&self, scope: Arc<Scope>) -> Interpreter<'a> {
+        Interpreter {
+            context: self.context,
+            scope: Some(scope),
+            custom_functions: self.custom_functions,
+            source: self.source,
+            regexps: self.regexps,
+            math_enabled: self.math_enabled,
+            bigint_enabled: self.bigint_enabled,
+            decimal: self.decimal,
+            non_json_policy: self.non_json_policy,
+            unsafe_integer_policy: self.unsafe_integer_policy,
+        }
+    }
+
+    pub(crate) fn evaluate_node(&self, expr: &Expr) -> Result<Value, EvaluationError> {
+        trace!("Evaluating Expr: {:?}", self.source_text(expr));
+
+        let res = self.evaluate_expr_kind(expr);
+
+        trace!("Expr {:?} => {:#?}", self.source_text(expr), res.as_ref());
+
+        // The innermost failing expression is the most precise location
+        res.map_err(|err| err.locate(expr.range, self.source))
+    }
+
+    fn evaluate_expr_kind(&self, expr: &Expr) -> Result<Value, EvaluationError> {
+        match &expr.kind {
+            ExprKind::Literal(Value::BigInt(_)) if !self.bigint_enabled => {
+                Err(EvaluationError::TypeError(
+                    format!(
+                        "BigInt literal '{}' is not supported unless BigInts are enabled",
+                        self.source_text(expr)
+                    ),
+                    None,
+                ))
+            }
+            ExprKind::Literal(value) => Ok(value.clone()),
+            ExprKind::DecimalLiteral { value, decimal } => Ok(match self.decimal {
+                Some(_) => Value::from(*decimal),
+                None => value.clone(),
+            }),
+            ExprKind::Identifier(name) => {
+                self.evaluate_by_name(name).map_err(EvaluationError::from)
+            }
+            ExprKind::Unary { op, expr: operand } => self.evaluate_prefix_expr(expr, *op, operand),
+            ExprKind::Binary { op, lhs, rhs } => self.evaluate_bin_expr(expr, *op, lhs, rhs),
+            ExprKind::Conditional { test, cons, alt } => self.evaluate_cond_expr(test, cons, alt),
+            // A chain that short-circuits on a nullish `?.` link evaluates to undefined
+            ExprKind::Member { .. } | ExprKind::Call { .. } => {
+                Ok(self.evaluate_chain(expr)?.unwrap_or_default())
+            }
+            ExprKind::Chain(inner) => Ok(self.evaluate_chain(inner)?.unwrap_or_default()),
+            ExprKind::Template { tag, quasis, exprs } => {
+                self.evaluate_template(expr, tag.as_deref(), quasis, exprs)
+            }
+            // The function captures the variables in scope where it is written
+            ExprKind::Arrow { params, body } => Ok(Value::Function(Function::new(
+                params.clone(),
+                body.clone(),
+                self.scope.clone(),
+                self.source_text(expr).to_string(),
+            ))),
+            ExprKind::Array(elements) => self.evaluate_array_expr(elements),
+            ExprKind::Object(properties) => self.evaluate_object_expr(properties),
+            ExprKind::RegExp(regexp) => Err(regexp::regexp_value_error(regexp)),
+            ExprKind::New { callee, .. } => match self.evaluate_regexp(expr)? {
+                Some(regexp) => Err(regexp::regexp_value_error(&regexp)),
+                None => Err(EvaluationError::TypeError(
+                    format!("'{}' is not a constructor", self.source_text(callee)),
+                    None,
+                )),
+            },
+        }
+    }
+
+    fn evaluate_template(
+        &self,
+        template: &Expr,
+        tag: Option<&str>,
+        quasis: &[String],
+        exprs: &[Expr],
+    ) -> Result<Value, EvaluationError> {
+        let values = exprs
+            .iter()
+            .map(|expr| self.evaluate_node(expr))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let Some(tag) = tag else {
+            let mut result = quasis[0].clone();
+            for (value, quasi) in values.iter().zip(&quasis[1..]) {
+                result.push_str(&self.to_property_key(value));
+                result.push_str(quasi);
+            }
+            return Ok(Value::String(result));
+        };
+
+        // A tag receives the string parts as an array followed by the substituted values
+        let func = self.custom_functions.get(tag).ok_or_else(|| NodeError {
+            message: format!("Function '{}' not found.", tag),
+            range: Some(template.range),
+            location: None,
+        })?;
+        let strings = quasis.iter().cloned().map(Value::String).collect();
+        let mut args = Vec::with_capacity(values.len() + 1);
+        args.push(Value::Array(strings));
+        args.extend(values);
+
+        func.call(&self.json_values(&args)?)
+            .map(Value::from)
+            .map_err(EvaluationError::from)
+    }
+
+    fn evaluate_array_expr(&self, elements: &[ArrayElement]) -> Result<Value, EvaluationError> {
+        let mut values = Vec::with_capacity(elements.len());
+        for element in elements {
+            match element {
+                ArrayElement::Expr(expr) => values.push(self.evaluate_node(expr)?),
+                ArrayElement::Hole => values.push(Value::Undefined),
+                ArrayElement::Spread(expr) => match self.evaluate_node(expr)? {
+                    Value::Array(items) => values.extend(items),
+                    // Strings are iterable and spread into their characters
+                    Value::String(s) => {
+                        values.extend(s.chars().map(|ch| Value::String(ch.to_string())))
+                    }
+                    other => {
+                        return Err(EvaluationError::TypeError(
+                            format!(
+                                "{} is not iterable (cannot spread '{}')",
+                                self.value_to_string(&other),
+                                self.source_text(expr)
+                            ),
+                            None,
+                        ))
+                    }
+                },
+            }
+        }
+        Ok(Value::Array(values))
+    }
+
+    fn evaluate_object_expr(
+        &self,
+        properties: &[ObjectProperty],
+    ) -> Result<Value, EvaluationError> {
+        let mut map = Map::new();
+        for property in properties {
+            match property {
+                ObjectProperty::KeyValue { key, value } => {
+                    let key = match key {
+                        PropertyKey::Static(key) => key.clone(),
+                        PropertyKey::Computed(expr) => {
+                            self.to_property_key(&self.evaluate_node(expr)?)
+                        }
+                    };
+                    map.insert(key, self.evaluate_node(value)?);
+                }
+                ObjectProperty::Spread(expr) => match self.evaluate_node(expr)? {
+                    Value::Object(entries) => map.extend(entries),
+                    Value::Array(items) => {
+                        for (index, item) in items.into_iter().enumerate() {
+                            map.insert(index.to_string(), item);
+                        }
+                    }
+                    Value::String(s) => {
+                        for (index, ch) in s.chars().enumerate() {
+                            map.insert(index.to_string(), Value::String(ch.to_string()));
+                        }
+                    }
+                    // Spreading nullish values or other primitives copies nothing, as in
+                    // JavaScript
+                    _ => {}
+                },
+            }
+        }
+        Ok(Value::Object(map))
+    }
+
+    /// The slice of the original expression that `expr` was lowered from.
+    fn source_text(&self, expr: &Expr) -> &str {
+        let start = usize::from(expr.range.start());
+        let end = usize::from(expr.range.end());
+        self.source.get(start..end).unwrap_or_default()
+    }
+
+    fn evaluate_bin_expr(
+        &self,
+        bin_expr: &Expr,
+        op: BinOp,
+        lhs: &Expr,
+        rhs: &Expr,
+    ) -> Result<Value, EvaluationError> {
+        let left_value = self.evaluate_node(lhs)?;
+
+        // Logical operators short-circuit and yield one of their operands, as in JavaScript:
+        // `user && user.name` never touches `user.name` when `user` is null, and
+        // `name || 'anonymous'` yields the string rather than `true`. `??` only falls
+        // through on null and undefined, so `discount ?? 10` keeps a discount of `0`.
+        match op {
+            BinOp::NullishCoalescing => {
+                return if left_value.is_nullish() {
+                    self.evaluate_node(rhs)
+                } else {
+                    Ok(left_value)
+                };
+            }
+            BinOp::LogicalAnd => {
+                return if self.to_boolean(&left_value)? {
+                    self.evaluate_node(rhs)
+                } else {
+                    Ok(left_value)
+                };
+            }
+            BinOp::LogicalOr => {
+                return if self.to_boolean(&left_value)? {
+                    Ok(left_value)
+                } else {
+                    self.evaluate_node(rhs)
+                };
+            }
+            _ => {}
+        }
+
+        let right_value = self.evaluate_node(rhs)?;
+
+        trace!("BinaryOp left_value {:?}", left_value);
+
+        trace!("BinaryOp right_value {:?}", right_value);
+
+        trace!("BinaryOp op {:?}", op);
+
+        let result = match op {
+            BinOp::Plus => self.add_values(left_value, right_value),
+            BinOp::Minus | BinOp::Times | BinOp::Divide | BinOp::Remainder | BinOp::Exponent => {
+                self.arithmetic(op, &left_value, &right_value)
+            }
+            BinOp::BitwiseAnd
+            | BinOp::BitwiseOr
+            | BinOp::BitwiseXor
+            | BinOp::LeftShift
+            | BinOp::RightShift
+            | BinOp::UnsignedRightShift => self.bitwise_values(op, &left_value, &right_value),
+            BinOp::Equality => Ok(Value::Bool(
+                self.abstract_equality(&left_value, &right_value),
+            )),
+            BinOp::Inequality => Ok(Value::Bool(
+                !self.abstract_equality(&left_value, &right_value),
+            )),
+            BinOp::StrictEquality => {
+                Ok(Value::Bool(self.strict_equality(&left_value, &right_value)))
+            }
+            BinOp::StrictInequality => Ok(Value::Bool(
+                !self.strict_equality(&left_value, &right_value),
+            )),
+            BinOp::GreaterThan => self.compare_values(&left_value, &right_value, Ordering::is_gt),
+            BinOp::LessThan => self.compare_values(&left_value, &right_value, Ordering::is_lt),
+            BinOp::GreaterThanOrEqual => {
+                self.compare_values(&left_value, &right_value, Ordering::is_ge)
+            }
+            BinOp::LessThanOrEqual => {
+                self.compare_values(&left_value, &right_value, Ordering::is_le)
+            }
+            BinOp::In => self.has_property(&left_value, &right_value),
+            _ => Err(EvaluationError::Node(NodeError {
+                message: "Unsupported binary operator".to_string(),
+                range: Some(bin_expr.range),
+                location: None,
+            })),
+        }?;
+
+        trace!("Binary Result: {:?}", result);
+
+        Ok(result)
+    }
+
+    fn add_values(&self, left: Value, right: Value) -> Result<Value, EvaluationError> {
+        // Strings are joined with the other operand converted as by `String(x)`, so
+        // `[1, 2] + ''` is "1,2" and `[] + {}` is "[object Object]"
+        match (left, right) {
+            (Value::String(l), r) => Ok(Value::String(l + &self.to_property_key(&r))),
+            (l, Value::String(r)) => Ok(Value::String(self.to_property_key(&l) + &r)),
+            // Arrays and objects are concatenated as strings
+            (l @ (Value::Array(_) | Value::Object(_)), r)
+            | (l, r @ (Value::Array(_) | Value::Object(_))) => Ok(Value::String(
+                self.to_property_key(&l) + &self.to_property_key(&r),
+            )),
+            // Any other primitives are added as numbers, so `true + 1` is 2 and
+            // `undefined + 1` is NaN
+            (l, r) => self.arithmetic(BinOp::Plus, &l, &r),
+        }
+    }
+
+    /// The numeric arithmetic operators. Two integers give an exact integer whenever
+    /// the result is whole and fits in 64 bits, so `1 + 1` is `2` and 64-bit IDs do not
+    /// lose precision; everything else is computed on decimals in decimal mode, or on
+    /// `f64`s as in JavaScript.
+    fn arithmetic(&self, op: BinOp, left: &Value, right: &Value) -> Result<Value, EvaluationError> {
+        match (left, right) {
+            (Value::BigInt(l), Value::BigInt(r)) => return bigint::arithmetic(op, l, r),
+            (Value::BigInt(_), _) | (_, Value::BigInt(_)) => {
+                return Err(bigint::mixed_types_error())
+            }
+            _ => {}
+        }
+
+        let l = self.to_numeric(left)?;
+        let r = self.to_numeric(right)?;
+        let exact = match (l.as_integer(), r.as_integer()) {
+            (Some(a), Some(b)) => Some(integer_arithmetic(op, a, b)),
+            _ => None,
+        };
+        if let Some(n) = exact.flatten().and_then(Number::from_integer) {
+            return Ok(Value::Number(n));
+        }
+        if let Some(n) = self.decimal_arithmetic(op, &l, &r) {
+            return Ok(Value::Number(n));
+        }
+        // Integer results beyond 64 bits are rounded, as in JavaScript
+        if let Some(i) = exact.flatten() {
+            if self.unsafe_integer_policy == UnsafeIntegerPolicy::Error {
+                return Err(EvaluationError::UnsafeInteger(i.to_string(), None));
+            }
+        }
+
+        let (a, b) = (self.number_to_f64(&l)?, self.number_to_f64(&r)?);
+        let result = match op {
+            BinOp::Plus => a + b,
+            BinOp::Minus => a - b,
+            BinOp::Times => a * b,
+            // Division by zero yields an infinity, or NaN for `0 / 0`, as in JavaScript
+            BinOp::Divide => a / b,
+            BinOp::Remainder => a % b,
+            BinOp::Exponent => math::js_pow(a, b),
+            _ => unreachable!("not an arithmetic operator: {:?}", op),
+        };
+        // Powers too large even for exact integer arithmetic
+        if exact.is_some()
+            && self.unsafe_integer_policy == UnsafeIntegerPolicy::Error
+            && result.fract() == 0.0
+            && result.abs() > MAX_SAFE_INTEGER
+        {
+            return Err(EvaluationError::UnsafeInteger(
+                Number::from_f64(result).to_string(),
+                None,
+            ));
+        }
+        Ok(number_value(result))
+    }
+
+    /// Bitwise and shift operators, which work on 32-bit integers like JavaScript's.
+    /// Two BigInts are combined at full width instead.
+    fn bitwise_values(
+        &self,
+        op: BinOp,
+        left: &Value,
+        right: &Value,
+    ) -> Result<Value, EvaluationError> {
+        match (left, right) {
+            (Value::BigInt(l), Value::BigInt(r)) => return bigint::bitwise(op, l, r),
+            (Value::BigInt(_), _) | (_, Value::BigInt(_)) => {
+                return Err(bigint::mixed_types_error())
+            }
+            _ => {}
+        }
+
+        let result = match op {
+            BinOp::BitwiseAnd => f64::from(self.to_int32(left)? & self.to_int32(right)?),
+            BinOp::BitwiseOr => f64::from(self.to_int32(left)? | self.to_int32(right)?),
+            BinOp::BitwiseXor => f64::from(self.to_int32(left)? ^ self.to_int32(right)?),
+            // Shift counts only use their low five bits
+            BinOp::LeftShift => f64::from(
+                self.to_int32(left)?
+                    .wrapping_shl(self.to_uint32(right)? & 31),
+            ),
+            BinOp::RightShift => f64::from(self.to_int32(left)? >> (self.to_uint32(right)? & 31)),
+            BinOp::UnsignedRightShift => {
+                f64::from(self.to_uint32(left)? >> (self.to_uint32(right)? & 31))
+            }
+            _ => unreachable!("not a bitwise operator: {:?}", op),
+        };
+        Ok(number_value(result))
+    }
+
+    /// The `key in object` operator, checking own keys of objects and the indices (and
+    /// `length`) of arrays.
+    fn has_property(&self, key: &Value, target: &Value) -> Result<Value, EvaluationError> {
+        let key = self.to_property_key(key);
+        let found = match target {
+            Value::Object(map) => map.contains_key(&key),
+            Value::Array(arr) => {
+                key == "length" || array_index(&key).is_some_and(|index| index < arr.len())
+            }
+            _ => {
+                return Err(EvaluationError::TypeError(
+                    format!(
+                        "Cannot use 'in' operator to search for '{}' in {}",
+                        key,
+                        self.value_to_string(target)
+                    ),
+                    None,
+                ))
+            }
+        };
+
+        Ok(Value::Bool(found))
+    }
+
+    /// The relational operators. Two strings are compared by UTF-16 code units, so
+    /// `'b' > 'a'` and `'10' < '9'`; anything else is compared as numbers. Integers and
+    /// BigInts are compared exactly, as are decimals in decimal mode, and NaN is
+    /// unordered, so every comparison with it is false.
+    fn compare_values(
+        &self,
+        left: &Value,
+        right: &Value,
+        accept: fn(Ordering) -> bool,
+    ) -> Result<Value, EvaluationError> {
+        let (left, right) = (self.to_primitive(left), self.to_primitive(right));
+        let (left, right) = (left.as_ref(), right.as_ref());
+
+        let ordering = match (left, right) {
+            (Value::String(l), Value::String(r)) => Some(l.encode_utf16().cmp(r.encode_utf16())),
+            (Value::BigInt(_), _) | (_, Value::BigInt(_)) => self.compare_bigint(left, right)?,
+            _ => {
+                let (l, r) = (self.to_numeric(left)?, self.to_numeric(right)?);
+                match (l.as_integer(), r.as_integer()) {
+                    (Some(a), Some(b)) => Some(a.cmp(&b)),
+                    _ => match self.compare_decimal(&l, &r) {
+                        Some(ordering) => Some(ordering),
+                        None => self
+                            .number_to_f64(&l)?
+                            .partial_cmp(&self.number_to_f64(&r)?),
+                    },
+                }
+            }
+        };
+        Ok(Value::Bool(ordering.is_some_and(accept)))
+    }
+
+    fn evaluate_prefix_expr(
+        &self,
+        prefix_expr: &Expr,
+        op: UnaryOp,
+        operand: &Expr,
+    ) -> Result<Value, EvaluationError> {
+        if op == UnaryOp::Typeof {
+            return self.evaluate_typeof(operand);
+        }
+
+        let expr_value = self.evaluate_node(operand)?;
+
+        let result = match op {
+            UnaryOp::LogicalNot => Value::Bool(!self.to_boolean(&expr_value)?),
+            UnaryOp::Void => Value::Undefined,
+            // `~x` is `-x - 1` for BigInts of any size
+            UnaryOp::BitwiseNot => match &expr_value {
+                Value::BigInt(b) => Value::BigInt(!b),
+                value => number_value(f64::from(!self.to_int32(value)?)),
+            },
+            UnaryOp::Minus => self.negate(&expr_value)?,
+            UnaryOp::Plus => Value::Number(self.to_numeric(&expr_value)?),
+            _ => {
+                return Err(EvaluationError::Node(NodeError {
+                    message: "Unsupported unary operator".to_string(),
+                    range: Some(prefix_expr.range),
+                    location: None,
+                }))
+            }
+        };
+        trace!("Prefix Result: {:?}", result);
+
+        Ok(result)
+    }
+
+    fn negate(&self, value: &Value) -> Result<Value, EvaluationError> {
+        if let Value::BigInt(b) = value {
+            return Ok(Value::BigInt(-b));
+        }
+        let n = self.to_numeric(value)?;
+        if n.is_decimal() {
+            return Ok(Value::from(-n.as_decimal().unwrap()));
+        }
+        // Negating an integer is exact, except that `-0` is a float
+        match n
+            .as_integer()
+            .filter(|i| *i != 0)
+            .and_then(|i| Number::from_integer(-i))
+        {
+            Some(negated) => Ok(Value::Number(negated)),
+            None => Ok(number_value(-self.number_to_f64(&n)?)),
+        }
+    }
+
+    fn evaluate_typeof(&self, operand: &Expr) -> Result<Value, EvaluationError> {
+        if self.evaluate_regexp(operand)?.is_some() {
+            return Ok(Value::String("object".to_string()));
+        }
+
+        // `None` stands for an identifier that is not defined at all
+        let value = match &operand.kind {
+            // `typeof` never throws on an undefined identifier
+            ExprKind::Identifier(name) => match self.evaluate_by_name(name) {
+                Ok(value) => Some(value),
+                Err(_)
+                    if self.custom_functions.contains_key(name)
+                        || self.global_function(name).is_some() =>
+                {
+                    return Ok(Value::String("function".to_string()));
+                }
+                Err(_) => match self.global_namespace(operand) {
+                    Some(namespace) => return Ok(Value::String(namespace.type_of().to_string())),
+                    None => None,
+                },
+            },
+            ExprKind::Member {
+                object,
+                property,
+                optional,
+            } => match self.evaluate_member_expr(object, property, *optional)? {
+                Some(ResolvableValue::BuiltInMethod { .. }) => {
+                    return Ok(Value::String("function".to_string()));
+                }
+                resolved => resolved.map(ResolvableValue::try_into_value).transpose()?,
+            },
+            ExprKind::Call { .. } => self.evaluate_chain(operand)?,
+            _ => Some(self.evaluate_node(operand)?),
+        };
+
+        let type_name = match value {
+            None | Some(Value::Undefined) => "undefined",
+            Some(Value::Bool(_)) => "boolean",
+            Some(Value::Number(_)) => "number",
+            Some(Value::String(_)) => "string",
+            Some(Value::BigInt(_)) => "bigint",
+            Some(Value::Function(_)) => "function",
+            Some(Value::Null | Value::Array(_) | Value::Object(_)) => "object",
+        };
+        trace!("Typeof Result: {:?}", type_name);
+
+        Ok(Value::String(type_name.to_string()))
+    }
+
+    fn evaluate_cond_expr(
+        &self,
+        test: &Expr,
+        cons: &Expr,
+        alt: &Expr,
+    ) -> Result<Value, EvaluationError> {
+        let cond_value = self.evaluate_node(test)?; // Returns EvaluationError
+        let cond_bool = self.to_boolean(&cond_value)?; // Returns EvaluationError
+
+        let result = if cond_bool {
+            self.evaluate_node(cons)? // Returns EvaluationError
+        } else {
+            self.evaluate_node(alt)? // Returns EvaluationError
+        };
+
+        trace!("Conditional Result: {:?}", result);
+
+        Ok(result)
+    }
+
+    /// Evaluates one link of a member/call chain, returning `None` when an optional
+    /// link further down the chain short-circuited.
+    fn evaluate_chain(&self, expr: &Expr) -> Result<Option<Value>, EvaluationError> {
+        match &expr.kind {
+            ExprKind::Member {
+                object,
+                property,
+                optional,
+            } => self
+                .evaluate_member_expr(object, property, *optional)?
+                .map(ResolvableValue::try_into_value)
+                .transpose(),
+            ExprKind::Call {
+                callee,
+                args,
+                optional,
+            } => {
+                if let Some(regexp) = self.evaluate_regexp(expr)? {
+                    return Err(regexp::regexp_value_error(&regexp));
+                }
+                self.evaluate_call_expr(callee, args, *optional)
+            }
+            _ => self.evaluate_node(expr).map(Some),
+        }
+    }
+
+    fn evaluate_member_expr(
+        &self,
+        object: &Expr,
+        property: &PropertyKey,
+        optional: bool,
+    ) -> Result<Option<ResolvableValue>, EvaluationError> {
+        if let Some(namespace) = self.global_namespace(object) {
+            let prop_name = self.property_name(property)?;
+            return Ok(Some(namespace.property(&prop_name)));
+        }
+        if let Some(regexp) = self.evaluate_regexp(object)? {
+            let prop_name = self.property_name(property)?;
+            return Ok(Some(ResolvableValue::Value(regexp::regexp_property(
+                &regexp, &prop_name,
+            ))));
+        }
+
+        // Evaluate the object part of the member expression
+        let Some(object_value) = self.evaluate_chain(object)? else {
+            return Ok(None);
+        };
+        if optional && object_value.is_nullish() {
+            return Ok(None);
+        }
+
+        let resolved = match property {
+            PropertyKey::Static(prop_name) => self.resolve_property(object_value, prop_name)?,
+            PropertyKey::Computed(prop_expr) => {
+                // `obj[key]` coerces the key to a string, so `arr[0]` and `arr['0']` agree
+                let key = self.evaluate_node(prop_expr)?;
+                self.resolve_property(object_value, &self.to_property_key(&key))?
+            }
+        };
+        Ok(Some(resolved))
+    }
+
+    /// The name of a static or computed property, coerced to a string.
+    fn property_name(&self, property: &PropertyKey) -> Result<String, EvaluationError> {
+        Ok(match property {
+            PropertyKey::Static(prop_name) => prop_name.clone(),
+            PropertyKey::Computed(prop_expr) => {
+                self.to_property_key(&self.evaluate_node(prop_expr)?)
+            }
+        })
+    }
+
+    /// Returns the built-in global object, such as `Math`, that `expr` refers to. A
+    /// variable with the same name shadows it, as it would in JavaScript.
+    fn global_namespace(&self, expr: &Expr) -> Option<Namespace> {
+        let ExprKind::Identifier(name) = &expr.kind else {
+            return None;
+        };
+        let namespace = Namespace::from_name(name)?;
+        if namespace == Namespace::Math && !self.math_enabled {
+            return None;
+        }
+        let shadowed = self.scope_variable(name).is_some() || self.context.get(name).is_some();
+        (!shadowed).then_some(namespace)
+    }
+
+    /// Maps the name of a global function such as `parseInt` to its built-in kind;
+    /// `BigInt` only exists when BigInts are enabled.
+    fn global_function(&self, name: &str) -> Option<BuiltInMethodKind> {
+        globals::global_function(name)
+            .filter(|method| *method != BuiltInMethodKind::GlobalBigInt || self.bigint_enabled)
+    }
+
+    /// Looks up `prop_name` on an already evaluated value, resolving array and string
+    /// indices as well as the built-in methods both `a.b` and `a['b']` share.
+    fn resolve_property(
+        &self,
+        object_value: Value,
+        prop_name: &str,
+    ) -> Result<ResolvableValue, EvaluationError> {
+        trace!(
+            "Member Expression: object_value={:?}, prop_name='{}'",
+            object_value,
+            prop_name
+        );
+
+        match object_value {
+            Value::Array(mut arr) => {
+                if let Some(index) = array_index(prop_name) {
+                    // Out of range indices read as undefined
+                    Ok(ResolvableValue::Value(if index < arr.len() {
+                        arr.swap_remove(index)
+                    } else {
+                        Value::Undefined
+                    }))
+                } else if prop_name == "length" {
+                    Ok(ResolvableValue::Value(number_value(arr.len() as f64)))
+                } else if let Some(method) = array::array_method(prop_name) {
+                    Ok(ResolvableValue::BuiltInMethod {
+                        object: Box::new(Value::Array(arr)),
+                        method,
+                    })
+                } else {
+                    // Accessing other properties like myArray.foo returns undefined in JS.
+                    Ok(ResolvableValue::Value(Value::Undefined))
+                }
+            }
+            Value::Object(map) => {
+                if prop_name == "hasOwnProperty" {
+                    Ok(ResolvableValue::BuiltInMethod {
+                        object: Box::new(Value::Object(map)),
+                        method: BuiltInMethodKind::ObjectHasOwnProperty,
+                    })
+                } else {
+                    Ok(ResolvableValue::Value(
+                        map.get(prop_name).cloned().unwrap_or_default(),
+                    ))
+                }
+            }
+            Value::String(s) if prop_name == "length" => Ok(ResolvableValue::Value(number_value(
+                string::string_length(&s) as f64,
+            ))),
+            Value::String(s) if string::string_method(prop_name).is_some() => {
+                Ok(ResolvableValue::BuiltInMethod {
+                    object: Box::new(Value::String(s)),
+                    method: string::string_method(prop_name).unwrap(),
+                })
+            }
+            Value::String(s) if array_index(prop_name).is_some() => {
+                // Strings are indexed by UTF-16 code unit, as in JavaScript
+                let index = array_index(prop_name).unwrap();
+                Ok(ResolvableValue::Value(
+                    s.encode_utf16()
+                        .nth(index)
+                        .map(|unit| Value::String(String::from_utf16_lossy(&[unit])))
+                        .unwrap_or_default(),
+                ))
+            }
+            _ => {
+                if prop_name == "length" {
+                    // Check for .length on non-array/non-object first
+                    Err(EvaluationError::TypeError(
+                        format!(
+                            "Cannot read property 'length' of non-array/non-object value: {}", // Clarified error
+                            self.value_to_string(&object_value)
+                        ),
+                        None,
+                    ))
+                } else {
+                    Err(EvaluationError::TypeError(format!(
+                        "Cannot read properties of null or primitive value: {} (trying to access property: {})",
+                        self.value_to_string(&object_value),
+                        prop_name
+                    ), None))
+                }
+            }
+        }
+    }
+
+    // Implement abstract equality similar to JavaScript (==)
+    // This includes type coercion
+    fn abstract_equality(&self, left: &Value, right: &Value) -> bool {
+        match (left, right) {
+            // null and undefined only equal each other
+            (Value::Null | Value::Undefined, Value::Null | Value::Undefined) => true,
+            (Value::Null | Value::Undefined, _) | (_, Value::Null | Value::Undefined) => false,
+
+            // Same type comparisons
+            (Value::Bool(l), Value::Bool(r)) => l == r,
+            (Value::String(l), Value::String(r)) => l == r,
+            // NaN is never equal to anything, including itself
+            (Value::Number(l), Value::Number(r)) => l == r,
+            (Value::BigInt(l), Value::BigInt(r)) => l == r,
+            // A function is only equal to itself
+            (Value::Function(l), Value::Function(r)) => l == r,
+
+            // Type coercion cases
+
+            // BigInt and a primitive compare by mathematical value, so `1n == 1`
+            (Value::BigInt(_), Value::Number(_) | Value::String(_) | Value::Bool(_))
+            | (Value::Number(_) | Value::String(_) | Value::Bool(_), Value::BigInt(_)) => {
+                matches!(self.compare_bigint(left, right), Ok(Some(Ordering::Equal)))
+            }
+
+            // Number and String: convert string to number
+            (Value::Number(l), Value::String(r)) | (Value::String(r), Value::Number(l)) => {
+                if let Ok(r_num) = self.to_number(&Value::String(r.clone())) {
+                    l.as_f64() == r_num
+                } else {
+                    false
+                }
+            }
+
+            // Boolean: convert to number and compare
+            (Value::Bool(b), other) | (other, Value::Bool(b)) => {
+                let bool_num: f64 = if *b { 1.0 } else { 0.0 };
+                if let Ok(other_num) = self.to_number(other) {
+                    bool_num == other_num
+                } else {
+                    false
+                }
+            }
+
+            // Array/Object comparisons (reference equality, always false for different instances)
+            _ => false,
+        }
+    }
+
+    // Implement strict equality (===)
+    // No type coercion
+    fn strict_equality(&self, left: &Value, right: &Value) -> bool {
+        match (left, right) {
+            (Value::Undefined, Value::Undefined) => true,
+            (Value::Null, Value::Null) => true,
+            (Value::Bool(l), Value::Bool(r)) => l == r,
+            (Value::String(l), Value::String(r)) => l == r,
+            // NaN is never equal to anything, including itself, and -0 equals 0
+            (Value::Number(l), Value::Number(r)) => l == r,
+            (Value::BigInt(l), Value::BigInt(r)) => l == r,
+            (Value::Function(l), Value::Function(r)) => l == r,
+            // Different types are never strictly equal
+            _ => false,
+        }
+    }
+
+    // Implement SameValueZero comparison (used by Array.includes)
+    // Similar to strict equality but NaN equals NaN
+    fn same_value_zero(&self, left: &Value, right: &Value) -> bool {
+        match (left, right) {
+            // Special case: NaN equals NaN in SameValueZero
+            (Value::Number(l), Value::Number(r)) => l == r || (l.is_nan() && r.is_nan()),
+            _ => self.strict_equality(left, right),
+        }
+    }
+
+    /// Looks up an arrow function parameter, from the innermost call outwards.
+    fn scope_variable(&self, name: &str) -> Option<&Value> {
+        self.scope.as_deref()?.get(name)
+    }
+
+    fn evaluate_by_name(&self, identifier_name: &str) -> Result<Value, NodeError> {
+        // Check for special JavaScript identifiers first
+        match identifier_name {
+            "Infinity" => return Ok(number_value(f64::INFINITY)),
+            "NaN" => return Ok(number_value(f64::NAN)),
+            "undefined" => return Ok(Value::Undefined),
+            _ => {}
+        }
+
+        // Arrow function parameters shadow context variables
+        if let Some(value) = self.scope_variable(identifier_name) {
+            return Ok(value.clone());
+        }
+
+        let identifier_value = self.context.get(identifier_name);
+
+        trace!("Identifier Value: {:#?}", identifier_value);
+
+        match identifier_value {
+            Some(value) => Ok(self.value_from_json(value)),
+            None => Err(NodeError {
+                message: format!("Identifier '{}' not found in context.", identifier_name),
+                range: None,
+                location: None,
+            }),
+        }
+    }
+
+    fn to_number(&self, value: &Value) -> Result<f64, EvaluationError> {
+        match value {
+            Value::Number(n) => self.number_to_f64(n),
+            // Only explicit conversions such as `Number(1n)` turn BigInts into numbers
+            Value::BigInt(_) => Err(EvaluationError::TypeError(
+                "Cannot convert a BigInt value to a number".to_string(),
+                None,
+            )),
+            // JavaScript behavior: invalid strings convert to NaN, blank strings to 0
+            Value::String(s) => Ok(globals::string_to_number(s)),
+            Value::Bool(b) => Ok(if *b { 1.0 } else { 0.0 }),
+            Value::Null => Ok(0.0),
+            Value::Undefined => Ok(f64::NAN),
+            Value::Array(arr) => {
+                // JavaScript: [] converts to 0, [x] converts to Number(x), otherwise NaN
+                if arr.is_empty() {
+                    Ok(0.0)
+                } else if arr.len() == 1 {
+                    self.to_number(&arr[0])
+                } else {
+                    Ok(f64::NAN)
+                }
+            }
+            Value::Object(_) => Ok(f64::NAN), // JavaScript: objects convert to NaN
+            Value::Function(_) => Ok(f64::NAN),
+        }
+    }
+
+    /// Like [`Interpreter::to_number`], but keeps integers exact.
+    fn to_numeric(&self, value: &Value) -> Result<Number, EvaluationError> {
+        match value {
+            Value::Number(n) => Ok(*n),
+            other => Ok(Number::from_computed(self.to_number(other)?)),
+        }
+    }
+
+    /// Converts a number to an `f64`, failing under [`UnsafeIntegerPolicy::Error`] when
+    /// that could round it.
+    fn number_to_f64(&self, n: &Number) -> Result<f64, EvaluationError> {
+        if self.unsafe_integer_policy == UnsafeIntegerPolicy::Error && n.is_unsafe_integer() {
+            return Err(EvaluationError::UnsafeInteger(n.to_string(), None));
+        }
+        Ok(n.as_f64())
+    }
+
+    /// JavaScript's ToInt32: truncates and wraps the number into a signed 32-bit integer.
+    fn to_int32(&self, value: &Value) -> Result<i32, EvaluationError> {
+        Ok(self.to_uint32(value)? as i32)
+    }
+
+    /// JavaScript's ToUint32: truncates and wraps the number modulo 2^32, with NaN and
+    /// the infinities becoming 0.
+    fn to_uint32(&self, value: &Value) -> Result<u32, EvaluationError> {
+        let num = self.to_number(value)?;
+        if !num.is_finite() {
+            return Ok(0);
+        }
+        Ok(num.trunc().rem_euclid(4294967296.0) as u32)
+    }
+
+    fn to_boolean(&self, value: &Value) -> Result<bool, EvaluationError> {
+        let result = match value {
+            Value::Bool(b) => *b,
+            Value::Null | Value::Undefined => false,
+            Value::Number(n) => {
+                let num = n.as_f64();
+                num != 0.0 && !num.is_nan()
+            }
+            Value::String(s) => !s.is_empty(),
+            Value::BigInt(b) => !b.is_zero(),
+            // JavaScript behavior: all arrays and objects are truthy, even if empty
+            Value::Array(_) => true,
+            Value::Object(_) => true,
+            Value::Function(_) => true,
+        };
+        Ok(result)
+    }
+
+    /// Coerces a value to an object key the way JavaScript's `ToPropertyKey` does,
+    /// so that `obj[1]` and `{ [1]: x }` both use the key `"1"`.
+    fn to_property_key(&self, value: &Value) -> String {
+        match value {
+            Value::String(s) => s.clone(),
+            Value::Number(n) => n.to_string(),
+            Value::BigInt(b) => b.to_string(),
+            Value::Bool(b) => b.to_string(),
+            Value::Null => "null".to_string(),
+            Value::Undefined => "undefined".to_string(),
+            Value::Array(items) => items
+                .iter()
+                .map(|item| match item {
+                    Value::Null | Value::Undefined => String::new(),
+                    item => self.to_property_key(item),
+                })
+                .collect::<Vec<_>>()
+                .join(","),
+            Value::Object(_) => "[object Object]".to_string(),
+            // JavaScript converts a function to its source text
+            Value::Function(function) => function.source().to_string(),
+        }
+    }
+
+    /// JavaScript's `ToPrimitive`: arrays, objects and functions become their string
+    /// form.
+    fn to_primitive<'v>(&self, value: &'v Value) -> Cow<'v, Value> {
+        match value {
+            Value::Array(_) | Value::Object(_) | Value::Function(_) => {
+                Cow::Owned(Value::String(self.to_property_key(value)))
+            }
+            value => Cow::Borrowed(value),
+        }
+    }
+
+    fn value_to_string(&self, value: &Value) -> String {
+        match value {
+            Value::String(s) => s.clone(),
+            Value::Number(n) => n.to_string(),
+            Value::BigInt(b) => b.to_string(),
+            Value::Bool(b) => b.to_string(),
+            Value::Null => "null".to_string(),
+            Value::Undefined => "undefined".to_string(),
+            Value::Array(_) => "[Array]".to_string(),
+            Value::Object(_) => "[Object]".to_string(),
+            Value::Function(function) => function.source().to_string(),
+        }
+    }
+
+    /// Converts the arguments of a custom function call to JSON.
+    fn json_values(&self, values: &[Value]) -> Result<Vec<serde_json::Value>, EvaluationError> {
+        values
+            .iter()
+            .map(|value| value.to_json(self.non_json_policy))
+            .collect()
+    }
+
+    /// Evaluates a call argument, turning functions into callable [`Lambda`]s and
+    /// regular expressions into [`RegExp`](crate::RegExp)s instead of values.
+    fn evaluate_argument(&self, expr: &Expr) -> Result<Argument<'_, Value>, EvaluationError> {
+        match self.evaluate_regexp(expr)? {
+            Some(regexp) => Ok(Argument::RegExp(regexp)),
+            None => match self.evaluate_node(expr)? {
+                Value::Function(function) => Ok(Argument::Lambda(Lambda::new(self, function))),
+                value => Ok(Argument::Value(value)),
+            },
+        }
+    }
+
+    /// Resolves a relative index argument such as `slice`'s `start`: negative values
+    /// count back from the end, and the result is clamped to `0..=len`.
+    fn relative_index(
+        &self,
+        value: Option<&Value>,
+        len: usize,
+        default: usize,
+    ) -> Result<usize, EvaluationError> {
+        let index = match value {
+            None | Some(Value::Undefined) => return Ok(default),
+            Some(value) => integer_or_infinity(self.to_number(value)?),
+        };
+        let len = len as f64;
+        let index = if index < 0.0 {
+            (len + index).max(0.0)
+        } else {
+            index.min(len)
+        };
+        Ok(index as usize)
+    }
+
+    fn call_built_in_method(
+        &self,
+        object: Value,
+        method: BuiltInMethodKind,
+        args: Vec<Argument<'_, Value>>,
+    ) -> Result<Value, EvaluationError> {
+        match (object, method) {
+            (Value::Object(obj_map), BuiltInMethodKind::ObjectHasOwnProperty) => {
+                let evaluated_args = argument_values(args)?;
+                if evaluated_args.len() != 1 {
+                    return Err(EvaluationError::CustomFunction(
+                        CustomFuncError::ArityError {
+                            expected: 1,
+                            got: evaluated_args.len(),
+                        },
+                        None,
+                    ));
+                }
+                // Coerce argument to string, similar to JS
+                let prop_key_str = self.to_property_key(&evaluated_args[0]);
+                Ok(Value::Bool(obj_map.contains_key(&prop_key_str)))
+            }
+            // Only global functions are resolved without a receiver
+            (Value::Null, method) => self.call_static_function(method, args),
+            (Value::Array(arr), method) => self.call_array_method(arr, method, args),
+            (Value::String(s), method) => self.call_string_method(s, method, args),
+            // This should not happen if BuiltInMethod is constructed correctly in resolve_property
+            (object, method) => Err(EvaluationError::TypeError(
+                format!(
+                    "{:?} method called on an unsupported internal object: {}",
+                    method,
+                    self.value_to_string(&object)
+                ),
+                None,
+            )),
+        }
+    }
+
+    fn evaluate_call_expr(
+        &self,
+        callee: &Expr,
+        args: &[Expr],
+        optional: bool,
+    ) -> Result<Option<Value>, EvaluationError> {
+        // Arguments are only evaluated once the callee is known not to short-circuit
+        let evaluate_args = || -> Result<Vec<Argument<'_, Value>>, EvaluationError> {
+            args.iter().map(|arg| self.evaluate_argument(arg)).collect()
+        };
+
+        match &callee.kind {
+            ExprKind::Identifier(func_name) => {
+                // Arrow function parameters shadow functions with the same name
+                if let Some(value) = self.scope_variable(func_name) {
+                    self.call_function_value(value.clone(), callee, args, optional)
+                } else if let Some(func) = self.custom_functions.get(func_name) {
+                    // Handle custom functions (e.g., myFunc())
+                    let evaluated_args = evaluate_args()?;
+                    // Custom functions take and return JSON
+                    let result = if evaluated_args.iter().any(|arg| arg.as_value().is_none()) {
+                        let args = evaluated_args
+                            .into_iter()
+                            .map(|arg| arg.into_json(self.non_json_policy))
+                            .collect::<Result<Vec<_>, _>>()?;
+                        func.call_with_lambdas(&args)
+                    } else {
+                        func.call(&self.json_values(&argument_values(evaluated_args)?)?)
+                    };
+                    result
+                        .map(|value| Some(self.value_from_json(Cow::Owned(value))))
+                        .map_err(EvaluationError::from)
+                } else if let Some(method) = self.global_function(func_name) {
+                    self.call_static_function(method, evaluate_args()?)
+                        .map(Some)
+                } else if optional
+                    && self
+                        .evaluate_by_name(func_name)
+                        .is_ok_and(|value| value.is_nullish())
+                {
+                    // `maybeFn?.()` is undefined when `maybeFn` is null or undefined, but
+                    // a name that is not defined at all is still an error
+                    Ok(None)
+                } else {
+                    Err(EvaluationError::Node(NodeError {
+                        message: format!("Function '{}' not found.", func_name),
+                        range: Some(callee.range),
+                        location: None,
+                    }))
+                }
+            }
+            ExprKind::Member {
+                object,
+                property,
+                optional: optional_member,
+            } => {
+                // `/a/.test(s)` and `new RegExp(p).exec(s)`
+                if let Some(regexp) = self.evaluate_regexp(object)? {
+                    let method = self.property_name(property)?;
+                    return self
+                        .call_regexp_method(&regexp, &method, evaluate_args()?)
+                        .map(Some);
+                }
+
+                // Handle method calls (e.g., myArray.includes())
+                let Some(resolvable_callee) =
+                    self.evaluate_member_expr(object, property, *optional_member)?
+                else {
+                    return Ok(None);
+                };
+                match resolvable_callee {
+                    ResolvableValue::BuiltInMethod { object, method } => self
+                        .call_built_in_method(*object, method, evaluate_args()?)
+                        .map(Some),
+                    // `obj.fn(x)` where `fn` holds an arrow function
+                    ResolvableValue::Value(value) => {
+                        self.call_function_value(value, callee, args, optional)
+                    }
+                }
+            }
+            // Any other callee, such as `(x => x * 2)(3)` or `(c ? f : g)(x)`
+            _ => {
+                let value = self.evaluate_node(callee)?;
+                self.call_function_value(value, callee, args, optional)
+            }
+        }
+    }
+
+    /// Calls the value a callee evaluated to, which has to be a function unless the call
+    /// is optional and the value is null or undefined.
+    fn call_function_value(
+        &self,
+        value: Value,
+        callee: &Expr,
+        args: &[Expr],
+        optional: bool,
+    ) -> Result<Option<Value>, EvaluationError> {
+        match value {
+            Value::Function(function) => {
+                let args = args
+                    .iter()
+                    .map(|arg| self.evaluate_argument(arg))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Lambda::new(self, function)
+                    .call_value(&argument_values(args)?)
+                    .map(Some)
+            }
+            value if optional && value.is_nullish() => Ok(None),
+            value => Err(EvaluationError::TypeError(
+                format!(
+                    "'{}' (resulting from expression '{}') is not a function.",
+                    self.value_to_string(&value),
+                    self.source_text(callee)
+                ),
+                None,
+            )),
+        }
+    }
+}
+
+/// Unwraps arguments for callees that only take values, rejecting regular expressions.
+fn argument_values(args: Vec<Argument<'_, Value>>) -> Result<Vec<Value>, EvaluationError> {
+    args.iter().map(argument_value).collect()
+}
+
+/// Parses a canonical array index such as `"0"` or `"12"` (but not `"01"` or `"1.5"`).
+fn array_index(key: &str) -> Option<usize> {
+    let index = key.parse::<usize>().ok()?;
+    (index.to_string() == key).then_some(index)
+}
+
+/// JavaScript's ToIntegerOrInfinity: truncates towards zero, with NaN becoming 0.
+fn integer_or_infinity(n: f64) -> f64 {
+    if n.is_nan() {
+        0.0
+    } else {
+        n.trunc()
+    }
+}
+
+/// Applies an arithmetic operator to two integers. Returns `None` when the result is
+/// not a whole number, or is `-0`, and has to be computed on floats instead.
+fn integer_arithmetic(op: BinOp, a: i128, b: i128) -> Option<i128> {
+    match op {
+        BinOp::Plus => a.checked_add(b),
+        BinOp::Minus => a.checked_sub(b),
+        // `0 * -1` is -0
+        BinOp::Times if (a == 0 && b < 0) || (a < 0 && b == 0) => None,
+        BinOp::Times => a.checked_mul(b),
+        BinOp::Divide if b == 0 || a % b != 0 || (a == 0 && b < 0) => None,
+        BinOp::Divide => Some(a / b),
+        // `-4 % 2` is -0
+        BinOp::Remainder if b == 0 || (a < 0 && a % b == 0) => None,
+        BinOp::Remainder => Some(a % b),
+        BinOp::Exponent => a.checked_pow(u32::try_from(b).ok()?),
+        _ => None,
+    }
+}
+
+fn number_value(n: f64) -> Value {
+    Value::Number(Number::from_computed(n))
+}
+
+fn check_arity(
+    args: &[Argument<'_, Value>],
+    min: usize,
+    max: usize,
+) -> Result<(), EvaluationError> {
+    let got = args.len();
+    if got < min || got > max {
+        return Err(EvaluationError::CustomFunction(
+            CustomFuncError::ArityError {
+                expected: if got < min { min } else { max },
+                got,
+            },
+            None,
+        ));
+    }
+    Ok(())
+}
+
+fn values_with_arity(
+    args: Vec<Argument<'_, Value>>,
+    min: usize,
+    max: usize,
+) -> Result<Vec<Value>, EvaluationError> {
+    check_arity(&args, min, max)?;
+    argument_values(args)
+}
+
+fn argument_value(arg: &Argument<'_, Value>) -> Result<Value, EvaluationError> {
+    match arg {
+        Argument::Value(value) => Ok(value.clone()),
+        Argument::Lambda(lambda) => Ok(Value::Function(lambda.function().clone())),
+        Argument::RegExp(regexp) => Err(EvaluationError::TypeError(
+            format!("Unexpected regular expression argument {}", regexp),
+            None,
+        )),
+    }
+}
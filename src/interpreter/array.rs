@@ -0,0 +1,483 @@
+use super::{
+    argument_value, argument_values, check_arity, integer_or_infinity, number_value,
+    values_with_arity, Interpreter,
+};
+use crate::Value;
+use crate::{Argument, BuiltInMethodKind, EvaluationError, Lambda};
+use std::cmp::Ordering;
+
+/// Maps an `Array.prototype` method name to its built-in kind.
+pub(super) fn array_method(name: &str) -> Option<BuiltInMethodKind> {
+    let method = match name {
+        "includes" => BuiltInMethodKind::ArrayIncludes,
+        "map" => BuiltInMethodKind::ArrayMap,
+        "filter" => BuiltInMethodKind::ArrayFilter,
+        "find" => BuiltInMethodKind::ArrayFind,
+        "findIndex" => BuiltInMethodKind::ArrayFindIndex,
+        "some" => BuiltInMethodKind::ArraySome,
+        "every" => BuiltInMethodKind::ArrayEvery,
+        "reduce" => BuiltInMethodKind::ArrayReduce,
+        "indexOf" => BuiltInMethodKind::ArrayIndexOf,
+        "lastIndexOf" => BuiltInMethodKind::ArrayLastIndexOf,
+        "slice" => BuiltInMethodKind::ArraySlice,
+        "concat" => BuiltInMethodKind::ArrayConcat,
+        "join" => BuiltInMethodKind::ArrayJoin,
+        "flat" => BuiltInMethodKind::ArrayFlat,
+        "flatMap" => BuiltInMethodKind::ArrayFlatMap,
+        "at" => BuiltInMethodKind::ArrayAt,
+        "sort" => BuiltInMethodKind::ArraySort,
+        "toSorted" => BuiltInMethodKind::ArrayToSorted,
+        "reverse" => BuiltInMethodKind::ArrayReverse,
+        "toReversed" => BuiltInMethodKind::ArrayToReversed,
+        "keys" => BuiltInMethodKind::ArrayKeys,
+        "entries" => BuiltInMethodKind::ArrayEntries,
+        _ => return None,
+    };
+    Some(method)
+}
+
+pub(super) const ARRAY_FUNCTIONS: &[(&str, BuiltInMethodKind)] = &[
+    ("from", BuiltInMethodKind::ArrayFrom),
+    ("isArray", BuiltInMethodKind::ArrayIsArray),
+    ("of", BuiltInMethodKind::ArrayOf),
+];
+
+/// The longest array `Array.from` builds from an array-like such as `{ length: n }`,
+/// so that an untrusted rule cannot allocate without bound.
+const MAX_ARRAY_LIKE_LENGTH: f64 = (1 << 20) as f64;
+
+impl Interpreter<'_> {
+    /// Calls an `Array` static function.
+    pub(super) fn call_array_function(
+        &self,
+        method: BuiltInMethodKind,
+        args: Vec<Argument<'_, Value>>,
+    ) -> Result<Value, EvaluationError> {
+        match method {
+            BuiltInMethodKind::ArrayIsArray => {
+                let args = values_with_arity(args, 1, 1)?;
+                Ok(Value::Bool(args[0].is_array()))
+            }
+            BuiltInMethodKind::ArrayOf => Ok(Value::Array(argument_values(args)?)),
+            BuiltInMethodKind::ArrayFrom => {
+                check_arity(&args, 1, 2)?;
+                let map_fn = match args.get(1) {
+                    Some(Argument::Lambda(lambda)) => Some(lambda),
+                    Some(Argument::Value(Value::Undefined)) | None => None,
+                    Some(other) => {
+                        return Err(EvaluationError::TypeError(
+                            format!(
+                                "{} is not a function",
+                                self.value_to_string(&argument_value(other)?)
+                            ),
+                            None,
+                        ))
+                    }
+                };
+                let items = self.array_from_items(argument_value(&args[0])?)?;
+                match map_fn {
+                    Some(map_fn) => items
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, item)| map_fn.call_value(&[item, number_value(i as f64)]))
+                        .collect::<Result<Vec<_>, _>>()
+                        .map(Value::Array),
+                    None => Ok(Value::Array(items)),
+                }
+            }
+            method => Err(EvaluationError::TypeError(
+                format!("{:?} is not an Array function.", method),
+                None,
+            )),
+        }
+    }
+
+    /// The elements `Array.from` takes from `source`: strings are split into code
+    /// points, and objects with a `length` are read as array-likes.
+    fn array_from_items(&self, source: Value) -> Result<Vec<Value>, EvaluationError> {
+        let items = match source {
+            Value::Array(items) => items,
+            Value::String(s) => s.chars().map(|c| Value::String(c.to_string())).collect(),
+            Value::Object(map) => {
+                let length = match map.get("length") {
+                    Some(length) => integer_or_infinity(self.to_number(length)?).max(0.0),
+                    None => 0.0,
+                };
+                if length > MAX_ARRAY_LIKE_LENGTH {
+                    return Err(EvaluationError::TypeError(
+                        format!("Invalid array length: {}", length),
+                        None,
+                    ));
+                }
+                (0..length as usize)
+                    .map(|i| map.get(&i.to_string()).cloned().unwrap_or_default())
+                    .collect()
+            }
+            Value::Null | Value::Undefined => {
+                return Err(EvaluationError::TypeError(
+                    format!("{} is not iterable", self.value_to_string(&source)),
+                    None,
+                ))
+            }
+            Value::Bool(_) | Value::Number(_) | Value::BigInt(_) => Vec::new(),
+            // A function's `length` is its number of parameters
+            Value::Function(function) => vec![Value::Undefined; function.arity()],
+        };
+        Ok(items)
+    }
+
+    /// Calls an `Array.prototype` method on `arr`.
+    ///
+    /// None of these mutate anything: `sort` and `reverse` behave like `toSorted` and
+    /// `toReversed`, since the array is always a copy of the context value.
+    pub(super) fn call_array_method(
+        &self,
+        arr: Vec<Value>,
+        method: BuiltInMethodKind,
+        args: Vec<Argument<'_, Value>>,
+    ) -> Result<Value, EvaluationError> {
+        match method {
+            BuiltInMethodKind::ArrayIncludes => {
+                let args = values_with_arity(args, 1, 2)?;
+                let start = self.relative_index(args.get(1), arr.len(), 0)?;
+                // JavaScript Array.includes uses SameValueZero comparison
+                // which is similar to strict equality but treats NaN as equal to NaN
+                let found = arr
+                    .iter()
+                    .skip(start)
+                    .any(|item| self.same_value_zero(item, &args[0]));
+                Ok(Value::Bool(found))
+            }
+            BuiltInMethodKind::ArrayMap => {
+                let callback = self.callback_arg(&args, 1)?;
+                let mapped = arr
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| self.call_element_callback(callback, item, i, &arr))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Value::Array(mapped))
+            }
+            BuiltInMethodKind::ArrayFilter => {
+                let callback = self.callback_arg(&args, 1)?;
+                let mut kept = Vec::new();
+                for (i, item) in arr.iter().enumerate() {
+                    if self.to_boolean(&self.call_element_callback(callback, item, i, &arr)?)? {
+                        kept.push(item.clone());
+                    }
+                }
+                Ok(Value::Array(kept))
+            }
+            BuiltInMethodKind::ArrayFind | BuiltInMethodKind::ArrayFindIndex => {
+                let callback = self.callback_arg(&args, 1)?;
+                for (i, item) in arr.iter().enumerate() {
+                    if self.to_boolean(&self.call_element_callback(callback, item, i, &arr)?)? {
+                        return Ok(if method == BuiltInMethodKind::ArrayFind {
+                            item.clone()
+                        } else {
+                            number_value(i as f64)
+                        });
+                    }
+                }
+                // `find` yields undefined when nothing matches
+                Ok(if method == BuiltInMethodKind::ArrayFind {
+                    Value::Undefined
+                } else {
+                    number_value(-1.0)
+                })
+            }
+            BuiltInMethodKind::ArraySome | BuiltInMethodKind::ArrayEvery => {
+                let callback = self.callback_arg(&args, 1)?;
+                // `some` stops at the first match, `every` at the first mismatch
+                let stop_on = method == BuiltInMethodKind::ArraySome;
+                for (i, item) in arr.iter().enumerate() {
+                    if self.to_boolean(&self.call_element_callback(callback, item, i, &arr)?)?
+                        == stop_on
+                    {
+                        return Ok(Value::Bool(stop_on));
+                    }
+                }
+                Ok(Value::Bool(!stop_on))
+            }
+            BuiltInMethodKind::ArrayReduce => {
+                let callback = self.callback_arg(&args, 2)?;
+                let mut items = arr.iter().enumerate();
+                let mut accumulator = match args.get(1) {
+                    Some(initial) => argument_value(initial)?,
+                    None => match items.next() {
+                        Some((_, first)) => first.clone(),
+                        None => {
+                            return Err(EvaluationError::TypeError(
+                                "Reduce of empty array with no initial value".to_string(),
+                                None,
+                            ))
+                        }
+                    },
+                };
+                for (i, item) in items {
+                    let mut call_args = vec![accumulator, item.clone(), number_value(i as f64)];
+                    if callback.arity() > 3 {
+                        call_args.push(Value::Array(arr.clone()));
+                    }
+                    accumulator = callback.call_value(&call_args)?;
+                }
+                Ok(accumulator)
+            }
+            BuiltInMethodKind::ArrayIndexOf => {
+                let args = values_with_arity(args, 1, 2)?;
+                let start = self.relative_index(args.get(1), arr.len(), 0)?;
+                let position = arr
+                    .iter()
+                    .skip(start)
+                    .position(|item| self.strict_equality(item, &args[0]))
+                    .map(|offset| (start + offset) as f64);
+                Ok(number_value(position.unwrap_or(-1.0)))
+            }
+            BuiltInMethodKind::ArrayLastIndexOf => {
+                let args = values_with_arity(args, 1, 2)?;
+                // The search runs backwards from `fromIndex`, which defaults to the end
+                let from = match args.get(1) {
+                    Some(from) => {
+                        let from = integer_or_infinity(self.to_number(from)?);
+                        if from < 0.0 {
+                            arr.len() as f64 + from
+                        } else {
+                            from.min(arr.len() as f64 - 1.0)
+                        }
+                    }
+                    None => arr.len() as f64 - 1.0,
+                };
+                if from < 0.0 {
+                    return Ok(number_value(-1.0));
+                }
+                let position = arr[..=from as usize]
+                    .iter()
+                    .rposition(|item| self.strict_equality(item, &args[0]))
+                    .map(|i| i as f64);
+                Ok(number_value(position.unwrap_or(-1.0)))
+            }
+            BuiltInMethodKind::ArraySlice => {
+                let args = values_with_arity(args, 0, 2)?;
+                let start = self.relative_index(args.first(), arr.len(), 0)?;
+                let end = self.relative_index(args.get(1), arr.len(), arr.len())?;
+                Ok(Value::Array(if start < end {
+                    arr[start..end].to_vec()
+                } else {
+                    Vec::new()
+                }))
+            }
+            BuiltInMethodKind::ArrayConcat => {
+                let mut result = arr;
+                for arg in argument_values(args)? {
+                    match arg {
+                        Value::Array(items) => result.extend(items),
+                        other => result.push(other),
+                    }
+                }
+                Ok(Value::Array(result))
+            }
+            BuiltInMethodKind::ArrayJoin => {
+                let args = values_with_arity(args, 0, 1)?;
+                let separator = match args.first() {
+                    None | Some(Value::Undefined) => ",".to_string(),
+                    Some(separator) => self.to_property_key(separator),
+                };
+                // Null and undefined become empty strings, everything else is converted
+                // like a key
+                let parts: Vec<String> = arr
+                    .iter()
+                    .map(|item| match item {
+                        Value::Null | Value::Undefined => String::new(),
+                        item => self.to_property_key(item),
+                    })
+                    .collect();
+                Ok(Value::String(parts.join(&separator)))
+            }
+            BuiltInMethodKind::ArrayFlat => {
+                let args = values_with_arity(args, 0, 1)?;
+                let depth = match args.first() {
+                    None | Some(Value::Undefined) => 1.0,
+                    Some(depth) => integer_or_infinity(self.to_number(depth)?),
+                };
+                let mut result = Vec::new();
+                flatten_into(&mut result, arr, depth);
+                Ok(Value::Array(result))
+            }
+            BuiltInMethodKind::ArrayFlatMap => {
+                let callback = self.callback_arg(&args, 1)?;
+                let mut result = Vec::new();
+                for (i, item) in arr.iter().enumerate() {
+                    match self.call_element_callback(callback, item, i, &arr)? {
+                        Value::Array(items) => result.extend(items),
+                        other => result.push(other),
+                    }
+                }
+                Ok(Value::Array(result))
+            }
+            BuiltInMethodKind::ArrayAt => {
+                let args = values_with_arity(args, 0, 1)?;
+                // A missing index is undefined, which converts to 0
+                let index = args.first().unwrap_or(&Value::Undefined);
+                let index = integer_or_infinity(self.to_number(index)?);
+                let index = if index < 0.0 {
+                    arr.len() as f64 + index
+                } else {
+                    index
+                };
+                Ok(if index >= 0.0 && index < arr.len() as f64 {
+                    arr[index as usize].clone()
+                } else {
+                    Value::Undefined
+                })
+            }
+            BuiltInMethodKind::ArraySort | BuiltInMethodKind::ArrayToSorted => {
+                check_arity(&args, 0, 1)?;
+                let comparator = match args.first() {
+                    Some(Argument::Lambda(lambda)) => Some(lambda),
+                    Some(Argument::Value(Value::Undefined)) | None => None,
+                    Some(other) => {
+                        return Err(EvaluationError::TypeError(
+                            format!(
+                        "The comparison function must be either a function or undefined, got {}",
+                        self.value_to_string(&argument_value(other)?)
+                    ),
+                            None,
+                        ))
+                    }
+                };
+                self.sort_values(arr, comparator).map(Value::Array)
+            }
+            BuiltInMethodKind::ArrayReverse | BuiltInMethodKind::ArrayToReversed => {
+                check_arity(&args, 0, 0)?;
+                let mut reversed = arr;
+                reversed.reverse();
+                Ok(Value::Array(reversed))
+            }
+            BuiltInMethodKind::ArrayKeys => {
+                check_arity(&args, 0, 0)?;
+                Ok(Value::Array(
+                    (0..arr.len()).map(|i| number_value(i as f64)).collect(),
+                ))
+            }
+            BuiltInMethodKind::ArrayEntries => {
+                check_arity(&args, 0, 0)?;
+                Ok(Value::Array(
+                    arr.into_iter()
+                        .enumerate()
+                        .map(|(i, item)| Value::Array(vec![number_value(i as f64), item]))
+                        .collect(),
+                ))
+            }
+            method => Err(EvaluationError::TypeError(
+                format!("{:?} method called on an array.", method),
+                None,
+            )),
+        }
+    }
+
+    /// Returns the callback a method like `map` takes as its first argument.
+    fn callback_arg<'b>(
+        &self,
+        args: &'b [Argument<'b, Value>],
+        max: usize,
+    ) -> Result<&'b Lambda<'b>, EvaluationError> {
+        check_arity(args, 1, max)?;
+        match &args[0] {
+            Argument::Lambda(lambda) => Ok(lambda),
+            other => Err(EvaluationError::TypeError(
+                format!(
+                    "{} is not a function",
+                    self.value_to_string(&argument_value(other)?)
+                ),
+                None,
+            )),
+        }
+    }
+
+    /// Calls a `map`/`filter`-style callback with `(element, index, array)`, only
+    /// copying the array when the callback declares a third parameter.
+    fn call_element_callback(
+        &self,
+        callback: &Lambda<'_>,
+        item: &Value,
+        index: usize,
+        arr: &[Value],
+    ) -> Result<Value, EvaluationError> {
+        let mut call_args = vec![item.clone(), number_value(index as f64)];
+        if callback.arity() > 2 {
+            call_args.push(Value::Array(arr.to_vec()));
+        }
+        callback.call_value(&call_args)
+    }
+
+    /// Stable sort matching `Array.prototype.sort`: without a comparator elements are
+    /// compared as strings by UTF-16 code units, and undefined always sorts last.
+    fn sort_values(
+        &self,
+        arr: Vec<Value>,
+        comparator: Option<&Lambda<'_>>,
+    ) -> Result<Vec<Value>, EvaluationError> {
+        let (mut values, undefined): (Vec<_>, Vec<_>) =
+            arr.into_iter().partition(|v| !v.is_undefined());
+
+        let mut compare = |a: &Value, b: &Value| -> Result<Ordering, EvaluationError> {
+            match comparator {
+                Some(comparator) => {
+                    let result =
+                        self.to_number(&comparator.call_value(&[a.clone(), b.clone()])?)?;
+                    Ok(if result < 0.0 {
+                        Ordering::Less
+                    } else if result > 0.0 {
+                        Ordering::Greater
+                    } else {
+                        Ordering::Equal
+                    })
+                }
+                None => Ok(self
+                    .to_property_key(a)
+                    .encode_utf16()
+                    .cmp(self.to_property_key(b).encode_utf16())),
+            }
+        };
+        // A hand-rolled merge sort, since `slice::sort_by` may panic when a user
+        // comparator is not a total order
+        values = merge_sort(values, &mut compare)?;
+        values.extend(undefined);
+        Ok(values)
+    }
+}
+
+fn merge_sort<F>(mut items: Vec<Value>, compare: &mut F) -> Result<Vec<Value>, EvaluationError>
+where
+    F: FnMut(&Value, &Value) -> Result<Ordering, EvaluationError>,
+{
+    if items.len() <= 1 {
+        return Ok(items);
+    }
+    let right = items.split_off(items.len() / 2);
+    let left = merge_sort(items, compare)?;
+    let right = merge_sort(right, compare)?;
+
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+        // Taking from the left on ties keeps the sort stable
+        if compare(l, r)? == Ordering::Greater {
+            merged.push(right.next().unwrap());
+        } else {
+            merged.push(left.next().unwrap());
+        }
+    }
+    merged.extend(left);
+    merged.extend(right);
+    Ok(merged)
+}
+
+fn flatten_into(result: &mut Vec<Value>, items: Vec<Value>, depth: f64) {
+    for item in items {
+        match item {
+            Value::Array(inner) if depth >= 1.0 => flatten_into(result, inner, depth - 1.0),
+            other => result.push(other),
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use super::string::is_js_whitespace;
+use super::Interpreter;
+use crate::{BigInt, EvaluationError, Number, Value};
+use num_traits::{FromPrimitive, ToPrimitive, Zero};
+use rslint_parser::ast::BinOp;
+use std::cmp::Ordering;
+
+/// The largest BigInt, in bits, that arithmetic may produce. Keeps `2n ** 1000000000n`
+/// in an untrusted rule from exhausting memory.
+const MAX_BIGINT_BITS: u64 = 1 << 20;
+
+/// Applies an arithmetic operator to two BigInts. The result is exact; division
+/// truncates towards zero, as in JavaScript.
+pub(super) fn arithmetic(op: BinOp, a: &BigInt, b: &BigInt) -> Result<Value, EvaluationError> {
+    let result = match op {
+        BinOp::Plus => a + b,
+        BinOp::Minus => a - b,
+        BinOp::Times if a.bits() + b.bits() > MAX_BIGINT_BITS => return Err(too_large_error()),
+        BinOp::Times => a * b,
+        BinOp::Divide | BinOp::Remainder if b.is_zero() => {
+            return Err(EvaluationError::TypeError(
+                "Division by zero".to_string(),
+                None,
+            ))
+        }
+        BinOp::Divide => a / b,
+        BinOp::Remainder => a % b,
+        BinOp::Exponent => {
+            if b < &BigInt::zero() {
+                return Err(EvaluationError::TypeError(
+                    "BigInt exponent must be non-negative".to_string(),
+                    None,
+                ));
+            }
+            match b.to_u32() {
+                Some(exponent)
+                    if a.bits().saturating_mul(u64::from(exponent)) <= MAX_BIGINT_BITS =>
+                {
+                    a.pow(exponent)
+                }
+                // Only 0, 1 and -1 can be raised to a huge power
+                _ if a.bits() <= 1 => {
+                    if a < &BigInt::zero() && (b % 2u32).is_zero() {
+                        -a
+                    } else {
+                        a.clone()
+                    }
+                }
+                _ => return Err(too_large_error()),
+            }
+        }
+        _ => unreachable!("not an arithmetic operator: {:?}", op),
+    };
+    Ok(Value::BigInt(result))
+}
+
+/// Applies a bitwise operator to two BigInts, treating them as infinitely wide two's
+/// complement integers as JavaScript does. There is no unsigned right shift, since a
+/// BigInt has no fixed width to fill with zeros.
+pub(super) fn bitwise(op: BinOp, a: &BigInt, b: &BigInt) -> Result<Value, EvaluationError> {
+    let result = match op {
+        BinOp::BitwiseAnd => a & b,
+        BinOp::BitwiseOr => a | b,
+        BinOp::BitwiseXor => a ^ b,
+        // A negative count shifts the other way
+        BinOp::LeftShift if b < &BigInt::zero() => shift_right(a, &-b),
+        BinOp::LeftShift => shift_left(a, b)?,
+        BinOp::RightShift if b < &BigInt::zero() => shift_left(a, &-b)?,
+        BinOp::RightShift => shift_right(a, b),
+        BinOp::UnsignedRightShift => {
+            return Err(EvaluationError::TypeError(
+                "BigInts have no unsigned right shift, use >> instead".to_string(),
+                None,
+            ))
+        }
+        _ => unreachable!("not a bitwise operator: {:?}", op),
+    };
+    Ok(Value::BigInt(result))
+}
+
+fn shift_left(a: &BigInt, count: &BigInt) -> Result<BigInt, EvaluationError> {
+    if a.is_zero() {
+        return Ok(BigInt::zero());
+    }
+    match count.to_u64() {
+        Some(count) if a.bits().saturating_add(count) <= MAX_BIGINT_BITS => Ok(a << count as usize),
+        _ => Err(too_large_error()),
+    }
+}
+
+/// Shifts right, rounding towards negative infinity, so `-5n >> 1n` is `-3n`.
+fn shift_right(a: &BigInt, count: &BigInt) -> BigInt {
+    match count.to_u64() {
+        Some(count) if count < a.bits() => a >> count as usize,
+        // Every bit is shifted out
+        _ if a < &BigInt::zero() => BigInt::from(-1),
+        _ => BigInt::zero(),
+    }
+}
+
+pub(super) fn mixed_types_error() -> EvaluationError {
+    EvaluationError::TypeError(
+        "Cannot mix BigInt and other types, use explicit conversions".to_string(),
+        None,
+    )
+}
+
+fn too_large_error() -> EvaluationError {
+    EvaluationError::TypeError("Maximum BigInt size exceeded".to_string(), None)
+}
+
+/// Converts a BigInt to a number, exactly if it fits in 64 bits.
+pub(super) fn to_number(b: &BigInt) -> Number {
+    b.to_i128()
+        .and_then(Number::from_integer)
+        .unwrap_or_else(|| Number::from_f64(b.to_f64().unwrap_or(f64::NAN)))
+}
+
+/// JavaScript's `StringToBigInt`: decimal digits with an optional sign, or a `0x`, `0o`
+/// or `0b` prefixed literal. Surrounding whitespace is ignored and an empty string is
+/// `0n`.
+fn parse_bigint(s: &str) -> Option<BigInt> {
+    let s = s.trim_matches(is_js_whitespace);
+    if s.is_empty() {
+        return Some(BigInt::zero());
+    }
+    let radix = match s.get(..2) {
+        Some("0x" | "0X") => 16,
+        Some("0o" | "0O") => 8,
+        Some("0b" | "0B") => 2,
+        _ => 10,
+    };
+    let digits = if radix == 10 { s } else { &s[2..] };
+    let unsigned = digits.strip_prefix(['+', '-']).unwrap_or(digits);
+    if unsigned.is_empty()
+        || (radix != 10 && unsigned.len() != digits.len())
+        || !unsigned.chars().all(|c| c.is_digit(radix))
+    {
+        return None;
+    }
+    BigInt::parse_bytes(digits.as_bytes(), radix)
+}
+
+/// A value that a BigInt can be compared with exactly.
+enum Comparable {
+    BigInt(BigInt),
+    Float(f64),
+}
+
+impl Interpreter<'_> {
+    /// `BigInt(value)`: converts integers, whole numbers, integer strings and booleans.
+    pub(super) fn to_bigint(&self, value: &Value) -> Result<BigInt, EvaluationError> {
+        let not_convertible = || {
+            EvaluationError::TypeError(
+                format!("Cannot convert {} to a BigInt", self.value_to_string(value)),
+                None,
+            )
+        };
+        match value {
+            Value::BigInt(b) => Ok(b.clone()),
+            Value::Number(n) => match n.as_integer() {
+                Some(i) => Ok(BigInt::from(i)),
+                None if n.as_f64().fract() == 0.0 => Ok(BigInt::from_f64(n.as_f64()).unwrap()),
+                None => Err(EvaluationError::TypeError(
+                    format!(
+                    "The number {} cannot be converted to a BigInt because it is not an integer",
+                    n
+                ),
+                    None,
+                )),
+            },
+            Value::Bool(b) => Ok(BigInt::from(u8::from(*b))),
+            Value::String(s) => parse_bigint(s).ok_or_else(not_convertible),
+            _ => Err(not_convertible()),
+        }
+    }
+
+    /// Compares two values of which at least one is a BigInt, for the relational and
+    /// `==` operators. Numbers are compared by their exact value, and `None` means the
+    /// values are unordered, e.g. when one of them is NaN.
+    pub(super) fn compare_bigint(
+        &self,
+        left: &Value,
+        right: &Value,
+    ) -> Result<Option<Ordering>, EvaluationError> {
+        let ordering = match (self.comparable(left)?, self.comparable(right)?) {
+            (Comparable::BigInt(a), Comparable::BigInt(b)) => Some(a.cmp(&b)),
+            (Comparable::BigInt(a), Comparable::Float(b)) => compare_with_float(&a, b),
+            (Comparable::Float(a), Comparable::BigInt(b)) => {
+                compare_with_float(&b, a).map(Ordering::reverse)
+            }
+            (Comparable::Float(a), Comparable::Float(b)) => a.partial_cmp(&b),
+        };
+        Ok(ordering)
+    }
+
+    fn comparable(&self, value: &Value) -> Result<Comparable, EvaluationError> {
+        Ok(match value {
+            Value::BigInt(b) => Comparable::BigInt(b.clone()),
+            Value::Number(n) => match n.as_integer() {
+                Some(i) => Comparable::BigInt(BigInt::from(i)),
+                None => Comparable::Float(n.as_f64()),
+            },
+            Value::String(s) => match parse_bigint(s) {
+                Some(b) => Comparable::BigInt(b),
+                None => Comparable::Float(f64::NAN),
+            },
+            other => Comparable::Float(self.to_number(other)?),
+        })
+    }
+}
+
+fn compare_with_float(a: &BigInt, b: f64) -> Option<Ordering> {
+    if b.is_nan() {
+        return None;
+    }
+    if b.is_infinite() {
+        return Some(if b > 0.0 {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        });
+    }
+    // Equal integer parts are decided by the fraction: 1n < 1.5 and -1n > -1.5
+    match a.cmp(&BigInt::from_f64(b.trunc()).unwrap()) {
+        Ordering::Equal => 0.0.partial_cmp(&b.fract()),
+        ordering => Some(ordering),
+    }
+}
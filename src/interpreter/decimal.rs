@@ -0,0 +1,99 @@
+use super::Interpreter;
+use crate::value::parse_decimal;
+use crate::{Decimal, Number, Value};
+use rslint_parser::ast::BinOp;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+
+impl Interpreter<'_> {
+    /// Applies an arithmetic operator on decimals. The result keeps full precision; only
+    /// the final result of the expression is rounded, by [`Interpreter::round_result`].
+    /// Returns `None` outside decimal mode, and when an operand or the result has no
+    /// decimal form, e.g. for NaN, division by zero or overflow, so that the operation
+    /// falls back to `f64`s.
+    pub(super) fn decimal_arithmetic(&self, op: BinOp, l: &Number, r: &Number) -> Option<Number> {
+        self.decimal?;
+        let (a, b) = (to_decimal(l)?, to_decimal(r)?);
+        let result = match op {
+            BinOp::Plus => a.checked_add(b),
+            BinOp::Minus => a.checked_sub(b),
+            BinOp::Times => a.checked_mul(b),
+            BinOp::Divide => a.checked_div(b),
+            BinOp::Remainder => a.checked_rem(b),
+            // Powers stay on integers and floats
+            _ => None,
+        }?;
+        Some(Number::from_decimal(result))
+    }
+
+    /// Rounds the decimals in the result of an expression, including those inside
+    /// arrays and objects, to the configured scale. Rounding once at the end keeps
+    /// `1 / 3 * 3` at 1 rather than 0.99.
+    pub(crate) fn round_result(&self, value: Value) -> Value {
+        let Some(mode) = self.decimal else {
+            return value;
+        };
+        match value {
+            Value::Number(n) => match n.as_decimal() {
+                Some(d) => Value::from(d.round_dp_with_strategy(mode.scale, mode.rounding)),
+                None => Value::Number(n),
+            },
+            Value::Array(items) => Value::Array(
+                items
+                    .into_iter()
+                    .map(|item| self.round_result(item))
+                    .collect(),
+            ),
+            Value::Object(map) => Value::Object(
+                map.into_iter()
+                    .map(|(key, value)| (key, self.round_result(value)))
+                    .collect(),
+            ),
+            value => value,
+        }
+    }
+
+    /// Compares two numbers as decimals in decimal mode.
+    pub(super) fn compare_decimal(&self, l: &Number, r: &Number) -> Option<Ordering> {
+        self.decimal?;
+        Some(to_decimal(l)?.cmp(&to_decimal(r)?))
+    }
+
+    /// Converts a context value, reading fractional numbers as decimals in decimal mode.
+    pub(crate) fn value_from_json(&self, json: Cow<'_, serde_json::Value>) -> Value {
+        match (self.decimal, json) {
+            (Some(_), json) => decimal_value_from_json(&json),
+            (None, Cow::Borrowed(json)) => Value::from(json),
+            (None, Cow::Owned(json)) => Value::from(json),
+        }
+    }
+}
+
+/// The exact value of a number as a decimal. Floats, which only appear in decimal mode
+/// as the result of functions such as `Math.sqrt`, are read from their shortest digits.
+fn to_decimal(n: &Number) -> Option<Decimal> {
+    match n.as_decimal() {
+        Some(d) => Some(d),
+        None if n.as_f64().is_finite() => parse_decimal(&n.to_string()),
+        None => None,
+    }
+}
+
+fn decimal_value_from_json(json: &serde_json::Value) -> Value {
+    match json {
+        // With `arbitrary_precision`, `to_string` gives the number's original digits
+        serde_json::Value::Number(n) if n.is_f64() => match parse_decimal(&n.to_string()) {
+            Some(d) => Value::from(d),
+            None => Value::Number(Number::from(n)),
+        },
+        serde_json::Value::Array(items) => {
+            Value::Array(items.iter().map(decimal_value_from_json).collect())
+        }
+        serde_json::Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), decimal_value_from_json(value)))
+                .collect(),
+        ),
+        other => Value::from(other),
+    }
+}
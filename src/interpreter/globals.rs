@@ -0,0 +1,344 @@
+use super::string::is_js_whitespace;
+use super::{array, json, math, object, values_with_arity, Interpreter};
+use super::{bigint, number_value};
+use crate::value::MAX_SAFE_INTEGER;
+use crate::BigInt;
+use crate::Value;
+use crate::{Argument, BuiltInMethodKind, EvaluationError, ResolvableValue};
+use num_traits::ToPrimitive;
+
+/// Global objects whose properties are resolved by name, such as `Math.PI`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Namespace {
+    Array,
+    Json,
+    Math,
+    Number,
+    Object,
+}
+
+impl Namespace {
+    pub(super) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Array" => Some(Namespace::Array),
+            "JSON" => Some(Namespace::Json),
+            "Math" => Some(Namespace::Math),
+            "Number" => Some(Namespace::Number),
+            "Object" => Some(Namespace::Object),
+            _ => None,
+        }
+    }
+
+    /// What `typeof` reports: `JSON` and `Math` are plain objects, the others are
+    /// constructors.
+    pub(super) fn type_of(self) -> &'static str {
+        match self {
+            Namespace::Json | Namespace::Math => "object",
+            Namespace::Array | Namespace::Number | Namespace::Object => "function",
+        }
+    }
+
+    fn constants(self) -> &'static [(&'static str, f64)] {
+        match self {
+            Namespace::Math => math::MATH_CONSTANTS,
+            Namespace::Number => NUMBER_CONSTANTS,
+            Namespace::Array | Namespace::Json | Namespace::Object => &[],
+        }
+    }
+
+    fn functions(self) -> &'static [(&'static str, BuiltInMethodKind)] {
+        match self {
+            Namespace::Array => array::ARRAY_FUNCTIONS,
+            Namespace::Json => json::JSON_FUNCTIONS,
+            Namespace::Math => math::MATH_FUNCTIONS,
+            Namespace::Number => NUMBER_FUNCTIONS,
+            Namespace::Object => object::OBJECT_FUNCTIONS,
+        }
+    }
+
+    /// Resolves a property of the namespace to a constant or a callable function.
+    /// Unknown names are undefined, as they would be in JavaScript.
+    pub(super) fn property(self, name: &str) -> ResolvableValue {
+        if let Some((_, value)) = self.constants().iter().find(|(n, _)| *n == name) {
+            return ResolvableValue::Value(number_value(*value));
+        }
+        match self.functions().iter().find(|(n, _)| *n == name) {
+            Some((_, method)) => ResolvableValue::BuiltInMethod {
+                // Static functions do not use a receiver
+                object: Box::new(Value::Null),
+                method: method.clone(),
+            },
+            None => ResolvableValue::Value(Value::Undefined),
+        }
+    }
+}
+
+const NUMBER_CONSTANTS: &[(&str, f64)] = &[
+    ("EPSILON", f64::EPSILON),
+    ("MAX_SAFE_INTEGER", MAX_SAFE_INTEGER),
+    ("MIN_SAFE_INTEGER", -MAX_SAFE_INTEGER),
+    ("MAX_VALUE", f64::MAX),
+    ("MIN_VALUE", 5e-324),
+    ("POSITIVE_INFINITY", f64::INFINITY),
+    ("NEGATIVE_INFINITY", f64::NEG_INFINITY),
+    ("NaN", f64::NAN),
+];
+
+const NUMBER_FUNCTIONS: &[(&str, BuiltInMethodKind)] = &[
+    ("isInteger", BuiltInMethodKind::NumberIsInteger),
+    ("isSafeInteger", BuiltInMethodKind::NumberIsSafeInteger),
+    ("isNaN", BuiltInMethodKind::NumberIsNaN),
+    ("isFinite", BuiltInMethodKind::NumberIsFinite),
+    // `Number.parseInt` and `Number.parseFloat` are the global functions
+    ("parseInt", BuiltInMethodKind::GlobalParseInt),
+    ("parseFloat", BuiltInMethodKind::GlobalParseFloat),
+];
+
+const GLOBAL_FUNCTIONS: &[(&str, BuiltInMethodKind)] = &[
+    ("Number", BuiltInMethodKind::GlobalNumber),
+    ("String", BuiltInMethodKind::GlobalString),
+    ("Boolean", BuiltInMethodKind::GlobalBoolean),
+    ("parseInt", BuiltInMethodKind::GlobalParseInt),
+    ("parseFloat", BuiltInMethodKind::GlobalParseFloat),
+    ("isNaN", BuiltInMethodKind::GlobalIsNaN),
+    ("isFinite", BuiltInMethodKind::GlobalIsFinite),
+    ("BigInt", BuiltInMethodKind::GlobalBigInt),
+];
+
+/// Maps the name of a global function such as `parseInt` to its built-in kind.
+pub(super) fn global_function(name: &str) -> Option<BuiltInMethodKind> {
+    GLOBAL_FUNCTIONS
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, method)| method.clone())
+}
+
+fn contains_function(functions: &[(&str, BuiltInMethodKind)], method: &BuiltInMethodKind) -> bool {
+    functions.iter().any(|(_, m)| m == method)
+}
+
+impl Interpreter<'_> {
+    /// Calls a global function or a function of a global namespace, such as
+    /// `Math.max` or `Object.keys`.
+    pub(super) fn call_static_function(
+        &self,
+        method: BuiltInMethodKind,
+        args: Vec<Argument<'_, Value>>,
+    ) -> Result<Value, EvaluationError> {
+        if contains_function(math::MATH_FUNCTIONS, &method) {
+            self.call_math_function(method, args)
+        } else if contains_function(object::OBJECT_FUNCTIONS, &method) {
+            self.call_object_function(method, args)
+        } else if contains_function(array::ARRAY_FUNCTIONS, &method) {
+            self.call_array_function(method, args)
+        } else if contains_function(json::JSON_FUNCTIONS, &method) {
+            self.call_json_function(method, args)
+        } else {
+            self.call_global_function(method, args)
+        }
+    }
+
+    /// Calls a global conversion function such as `parseInt`, or one of the `Number`
+    /// static functions.
+    fn call_global_function(
+        &self,
+        method: BuiltInMethodKind,
+        args: Vec<Argument<'_, Value>>,
+    ) -> Result<Value, EvaluationError> {
+        let max = match method {
+            BuiltInMethodKind::GlobalParseInt => 2,
+            _ => 1,
+        };
+        let args = values_with_arity(args, 0, max)?;
+        // A missing argument is undefined
+        let arg = args.first().unwrap_or(&Value::Undefined);
+
+        let result = match method {
+            BuiltInMethodKind::GlobalNumber => match args.first() {
+                // `Number()` is 0, but `Number(undefined)` is NaN
+                None => number_value(0.0),
+                Some(Value::BigInt(b)) => Value::Number(bigint::to_number(b)),
+                Some(Value::Number(n)) => Value::Number(*n),
+                Some(arg) => number_value(self.to_number(arg)?),
+            },
+            BuiltInMethodKind::GlobalString => match args.first() {
+                None => Value::String(String::new()),
+                Some(arg) => Value::String(self.to_property_key(arg)),
+            },
+            BuiltInMethodKind::GlobalBoolean => Value::Bool(self.to_boolean(arg)?),
+            BuiltInMethodKind::GlobalBigInt => Value::BigInt(self.to_bigint(arg)?),
+            BuiltInMethodKind::GlobalParseInt => {
+                let radix = match args.get(1) {
+                    None | Some(Value::Undefined) => 0,
+                    Some(radix) => self.to_int32(radix)?,
+                };
+                number_value(parse_int(&self.to_property_key(arg), radix))
+            }
+            BuiltInMethodKind::GlobalParseFloat => {
+                number_value(parse_float(&self.to_property_key(arg)))
+            }
+            BuiltInMethodKind::GlobalIsNaN => Value::Bool(self.to_number(arg)?.is_nan()),
+            BuiltInMethodKind::GlobalIsFinite => Value::Bool(self.to_number(arg)?.is_finite()),
+            // Unlike the globals, the `Number` functions never coerce their argument
+            BuiltInMethodKind::NumberIsInteger => {
+                Value::Bool(as_number(arg).is_some_and(|n| n.is_finite() && n.trunc() == n))
+            }
+            BuiltInMethodKind::NumberIsSafeInteger => Value::Bool(
+                as_number(arg).is_some_and(|n| n.trunc() == n && n.abs() <= MAX_SAFE_INTEGER),
+            ),
+            BuiltInMethodKind::NumberIsNaN => Value::Bool(as_number(arg).is_some_and(f64::is_nan)),
+            BuiltInMethodKind::NumberIsFinite => {
+                Value::Bool(as_number(arg).is_some_and(f64::is_finite))
+            }
+            method => {
+                return Err(EvaluationError::TypeError(
+                    format!("{:?} is not a global function.", method),
+                    None,
+                ))
+            }
+        };
+        Ok(result)
+    }
+}
+
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => Some(n.as_f64()),
+        _ => None,
+    }
+}
+
+/// JavaScript's `parseInt`: parses the longest prefix of digits valid in `radix`, after
+/// optional whitespace, a sign and (for radix 16 or 0) a `0x` prefix.
+fn parse_int(input: &str, radix: i32) -> f64 {
+    let s = input.trim_start_matches(is_js_whitespace);
+    let (negative, s) = match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+
+    let has_hex_prefix = s.starts_with("0x") || s.starts_with("0X");
+    let (radix, s) = match radix {
+        0 if has_hex_prefix => (16, &s[2..]),
+        0 => (10, s),
+        16 if has_hex_prefix => (16, &s[2..]),
+        2..=36 => (radix as u32, s),
+        _ => return f64::NAN,
+    };
+
+    let digits: &str = match s.find(|c: char| !c.is_digit(radix)) {
+        Some(end) => &s[..end],
+        None => s,
+    };
+    if digits.is_empty() {
+        return f64::NAN;
+    }
+
+    let value = if radix == 10 {
+        // Let the standard library round long decimal strings correctly
+        digits.parse::<f64>().unwrap()
+    } else {
+        digits.chars().fold(0.0, |acc, c| {
+            acc * f64::from(radix) + f64::from(c.to_digit(radix).unwrap())
+        })
+    };
+    if negative {
+        -value
+    } else {
+        value
+    }
+}
+
+/// JavaScript's string-to-number conversion, used by `Number()` and arithmetic. Unlike
+/// `parseFloat`, the whole string must be a number: a decimal literal, `Infinity`, or an
+/// integer with a `0x`, `0o` or `0b` prefix. Blank strings are 0.
+pub(super) fn string_to_number(input: &str) -> f64 {
+    let s = input.trim_matches(is_js_whitespace);
+    if s.is_empty() {
+        return 0.0;
+    }
+
+    let radix = match s.get(..2) {
+        Some("0x" | "0X") => 16,
+        Some("0o" | "0O") => 8,
+        Some("0b" | "0B") => 2,
+        _ => 10,
+    };
+    if radix != 10 {
+        // Prefixed integers take no sign, so `-0x10` is NaN
+        let digits = &s[2..];
+        if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+            return f64::NAN;
+        }
+        return BigInt::parse_bytes(digits.as_bytes(), radix)
+            .and_then(|n| n.to_f64())
+            .unwrap_or(f64::NAN);
+    }
+
+    let unsigned = s.strip_prefix(['+', '-']).unwrap_or(s);
+    if unsigned == "Infinity" {
+        return if s.starts_with('-') {
+            f64::NEG_INFINITY
+        } else {
+            f64::INFINITY
+        };
+    }
+    // Rust also reads `inf`, `infinity` and `nan`, which JavaScript does not
+    let is_decimal_literal = unsigned
+        .bytes()
+        .all(|b| b.is_ascii_digit() || matches!(b, b'.' | b'e' | b'E' | b'+' | b'-'));
+    if !is_decimal_literal {
+        return f64::NAN;
+    }
+    s.parse::<f64>().unwrap_or(f64::NAN)
+}
+
+/// JavaScript's `parseFloat`: parses the longest prefix that forms a decimal literal
+/// (or `Infinity`) after optional whitespace.
+fn parse_float(input: &str) -> f64 {
+    let s = input.trim_start_matches(is_js_whitespace);
+    let bytes = s.as_bytes();
+    let mut end = 0;
+    if matches!(bytes.first(), Some(b'+' | b'-')) {
+        end += 1;
+    }
+    if s[end..].starts_with("Infinity") {
+        return if s.starts_with('-') {
+            f64::NEG_INFINITY
+        } else {
+            f64::INFINITY
+        };
+    }
+
+    let count_digits = |from: usize| {
+        bytes[from..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count()
+    };
+    let int_digits = count_digits(end);
+    end += int_digits;
+    let mut frac_digits = 0;
+    if bytes.get(end) == Some(&b'.') {
+        frac_digits = count_digits(end + 1);
+        if int_digits > 0 || frac_digits > 0 {
+            end += 1 + frac_digits;
+        }
+    }
+    if int_digits == 0 && frac_digits == 0 {
+        return f64::NAN;
+    }
+    // The exponent only counts if it has digits, so `1e` parses as 1
+    if matches!(bytes.get(end), Some(b'e' | b'E')) {
+        let mut exp_end = end + 1;
+        if matches!(bytes.get(exp_end), Some(b'+' | b'-')) {
+            exp_end += 1;
+        }
+        let exp_digits = count_digits(exp_end);
+        if exp_digits > 0 {
+            end = exp_end + exp_digits;
+        }
+    }
+    s[..end].parse::<f64>().unwrap_or(f64::NAN)
+}
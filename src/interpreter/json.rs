@@ -0,0 +1,181 @@
+use super::{integer_or_infinity, values_with_arity, Interpreter};
+use crate::Value;
+use crate::{Argument, BuiltInMethodKind, EvaluationError};
+use std::borrow::Cow;
+
+pub(super) const JSON_FUNCTIONS: &[(&str, BuiltInMethodKind)] = &[
+    ("parse", BuiltInMethodKind::JsonParse),
+    ("stringify", BuiltInMethodKind::JsonStringify),
+];
+
+impl Interpreter<'_> {
+    /// Calls `JSON.parse` or `JSON.stringify`.
+    pub(super) fn call_json_function(
+        &self,
+        method: BuiltInMethodKind,
+        args: Vec<Argument<'_, Value>>,
+    ) -> Result<Value, EvaluationError> {
+        match method {
+            BuiltInMethodKind::JsonParse => {
+                let args = values_with_arity(args, 1, 1)?;
+                // Parsed numbers are read as decimals in decimal mode, like the context
+                serde_json::from_str::<serde_json::Value>(&self.to_property_key(&args[0]))
+                    .map(|json| self.value_from_json(Cow::Owned(json)))
+                    .map_err(|err| EvaluationError::JsonParse(err, None))
+            }
+            BuiltInMethodKind::JsonStringify => {
+                let args = values_with_arity(args, 1, 3)?;
+                let allowed_keys = match args.get(1) {
+                    None | Some(Value::Null | Value::Undefined) => None,
+                    // An array replacer lists the object keys to keep
+                    Some(Value::Array(keys)) => {
+                        Some(keys.iter().map(|key| self.to_property_key(key)).collect())
+                    }
+                    Some(other) => {
+                        return Err(EvaluationError::TypeError(
+                            format!(
+                                "JSON.stringify replacer must be an array or null, got {}",
+                                self.value_to_string(other)
+                            ),
+                            None,
+                        ))
+                    }
+                };
+                let indent = match args.get(2) {
+                    // At most 10 characters of indentation, as in JavaScript
+                    Some(Value::Number(n)) => {
+                        let width = integer_or_infinity(n.as_f64());
+                        " ".repeat(width.clamp(0.0, 10.0) as usize)
+                    }
+                    Some(Value::String(s)) => s.chars().take(10).collect(),
+                    _ => String::new(),
+                };
+
+                // Like JavaScript, `JSON.stringify(undefined)` and `JSON.stringify(x => x)`
+                // are undefined rather than strings
+                if matches!(args[0], Value::Undefined | Value::Function(_)) {
+                    return Ok(Value::Undefined);
+                }
+                let mut out = String::new();
+                let stringifier = Stringifier {
+                    allowed_keys,
+                    indent,
+                };
+                stringifier.write(&mut out, &args[0], "")?;
+                Ok(Value::String(out))
+            }
+            method => Err(EvaluationError::TypeError(
+                format!("{:?} is not a JSON function.", method),
+                None,
+            )),
+        }
+    }
+}
+
+/// Serializes values the way `JSON.stringify` does, so numbers are written without a
+/// trailing `.0`, undefined object properties are left out and indentation matches
+/// JavaScript's output.
+struct Stringifier {
+    allowed_keys: Option<Vec<String>>,
+    indent: String,
+}
+
+impl Stringifier {
+    fn write(
+        &self,
+        out: &mut String,
+        value: &Value,
+        current_indent: &str,
+    ) -> Result<(), EvaluationError> {
+        match value {
+            // Undefined and function array elements are written as null
+            Value::Null | Value::Undefined | Value::Function(_) => out.push_str("null"),
+            Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+            Value::Number(n) => {
+                // NaN and the infinities have no JSON form
+                if n.as_f64().is_finite() {
+                    out.push_str(&n.to_string());
+                } else {
+                    out.push_str("null");
+                }
+            }
+            Value::String(s) => out.push_str(&serde_json::to_string(s).unwrap()),
+            // As in JavaScript, BigInts have no JSON form
+            Value::BigInt(_) => {
+                return Err(EvaluationError::TypeError(
+                    "Do not know how to serialize a BigInt".to_string(),
+                    None,
+                ))
+            }
+            Value::Array(items) => {
+                self.write_list(out, '[', ']', current_indent, items, |out, item, indent| {
+                    self.write(out, item, indent)
+                })?
+            }
+            Value::Object(map) => {
+                let entries: Vec<_> = map
+                    .iter()
+                    .filter(|(key, value)| {
+                        !matches!(value, Value::Undefined | Value::Function(_))
+                            && self.is_allowed(key)
+                    })
+                    .collect();
+                self.write_list(
+                    out,
+                    '{',
+                    '}',
+                    current_indent,
+                    &entries,
+                    |out, (key, value), indent| {
+                        out.push_str(&serde_json::to_string(key).unwrap());
+                        out.push(':');
+                        if !self.indent.is_empty() {
+                            out.push(' ');
+                        }
+                        self.write(out, value, indent)
+                    },
+                )?
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `key` is in the replacer array, if one was given.
+    fn is_allowed(&self, key: &str) -> bool {
+        match &self.allowed_keys {
+            Some(allowed) => allowed.iter().any(|k| k == key),
+            None => true,
+        }
+    }
+
+    fn write_list<T>(
+        &self,
+        out: &mut String,
+        open: char,
+        close: char,
+        current_indent: &str,
+        items: &[T],
+        mut write_item: impl FnMut(&mut String, &T, &str) -> Result<(), EvaluationError>,
+    ) -> Result<(), EvaluationError> {
+        out.push(open);
+        if !items.is_empty() {
+            let inner_indent = format!("{}{}", current_indent, self.indent);
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                if !self.indent.is_empty() {
+                    out.push('\n');
+                    out.push_str(&inner_indent);
+                }
+                write_item(out, item, &inner_indent)?;
+            }
+            if !self.indent.is_empty() {
+                out.push('\n');
+                out.push_str(current_indent);
+            }
+        }
+        out.push(close);
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+use super::{argument_values, number_value, Interpreter};
+use crate::Value;
+use crate::{Argument, BuiltInMethodKind, EvaluationError};
+use std::f64::consts;
+
+pub(super) const MATH_CONSTANTS: &[(&str, f64)] = &[
+    ("E", consts::E),
+    ("LN10", consts::LN_10),
+    ("LN2", consts::LN_2),
+    ("LOG10E", consts::LOG10_E),
+    ("LOG2E", consts::LOG2_E),
+    ("PI", consts::PI),
+    ("SQRT1_2", consts::FRAC_1_SQRT_2),
+    ("SQRT2", consts::SQRT_2),
+];
+
+pub(super) const MATH_FUNCTIONS: &[(&str, BuiltInMethodKind)] = &[
+    ("abs", BuiltInMethodKind::MathAbs),
+    ("acos", BuiltInMethodKind::MathAcos),
+    ("acosh", BuiltInMethodKind::MathAcosh),
+    ("asin", BuiltInMethodKind::MathAsin),
+    ("asinh", BuiltInMethodKind::MathAsinh),
+    ("atan", BuiltInMethodKind::MathAtan),
+    ("atanh", BuiltInMethodKind::MathAtanh),
+    ("atan2", BuiltInMethodKind::MathAtan2),
+    ("cbrt", BuiltInMethodKind::MathCbrt),
+    ("ceil", BuiltInMethodKind::MathCeil),
+    ("cos", BuiltInMethodKind::MathCos),
+    ("cosh", BuiltInMethodKind::MathCosh),
+    ("exp", BuiltInMethodKind::MathExp),
+    ("expm1", BuiltInMethodKind::MathExpm1),
+    ("floor", BuiltInMethodKind::MathFloor),
+    ("hypot", BuiltInMethodKind::MathHypot),
+    ("log", BuiltInMethodKind::MathLog),
+    ("log10", BuiltInMethodKind::MathLog10),
+    ("log1p", BuiltInMethodKind::MathLog1p),
+    ("log2", BuiltInMethodKind::MathLog2),
+    ("max", BuiltInMethodKind::MathMax),
+    ("min", BuiltInMethodKind::MathMin),
+    ("pow", BuiltInMethodKind::MathPow),
+    ("round", BuiltInMethodKind::MathRound),
+    ("sign", BuiltInMethodKind::MathSign),
+    ("sin", BuiltInMethodKind::MathSin),
+    ("sinh", BuiltInMethodKind::MathSinh),
+    ("sqrt", BuiltInMethodKind::MathSqrt),
+    ("tan", BuiltInMethodKind::MathTan),
+    ("tanh", BuiltInMethodKind::MathTanh),
+    ("trunc", BuiltInMethodKind::MathTrunc),
+];
+
+impl Interpreter<'_> {
+    /// Calls a `Math` function. Arguments are converted with `ToNumber`, and NaN and the
+    /// infinities propagate as they do in JavaScript. As there, missing arguments are
+    /// undefined (so the result is NaN) and extra arguments are ignored.
+    pub(super) fn call_math_function(
+        &self,
+        method: BuiltInMethodKind,
+        args: Vec<Argument<'_, Value>>,
+    ) -> Result<Value, EvaluationError> {
+        let arity = match method {
+            BuiltInMethodKind::MathMax
+            | BuiltInMethodKind::MathMin
+            | BuiltInMethodKind::MathHypot => args.len(),
+            BuiltInMethodKind::MathAtan2 | BuiltInMethodKind::MathPow => 2,
+            _ => 1,
+        };
+        let mut args = argument_values(args)?;
+        args.resize(arity, Value::Undefined);
+        let nums = args
+            .iter()
+            .map(|arg| self.to_number(arg))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let result = match method {
+            BuiltInMethodKind::MathMax => nums.iter().try_fold(f64::NEG_INFINITY, |acc, &n| {
+                // Unlike `f64::max`, any NaN argument makes the result NaN
+                (!n.is_nan()).then(|| acc.max(n))
+            }),
+            BuiltInMethodKind::MathMin => nums
+                .iter()
+                .try_fold(f64::INFINITY, |acc, &n| (!n.is_nan()).then(|| acc.min(n))),
+            BuiltInMethodKind::MathHypot => {
+                // An infinite argument wins over NaN
+                if nums.iter().any(|n| n.is_infinite()) {
+                    Some(f64::INFINITY)
+                } else {
+                    Some(nums.iter().map(|n| n * n).sum::<f64>().sqrt())
+                }
+            }
+            BuiltInMethodKind::MathAtan2 => Some(nums[0].atan2(nums[1])),
+            BuiltInMethodKind::MathPow => Some(js_pow(nums[0], nums[1])),
+            method => {
+                let x = nums[0];
+                Some(match method {
+                    BuiltInMethodKind::MathAbs => x.abs(),
+                    BuiltInMethodKind::MathAcos => x.acos(),
+                    BuiltInMethodKind::MathAcosh => x.acosh(),
+                    BuiltInMethodKind::MathAsin => x.asin(),
+                    BuiltInMethodKind::MathAsinh => x.asinh(),
+                    BuiltInMethodKind::MathAtan => x.atan(),
+                    BuiltInMethodKind::MathAtanh => x.atanh(),
+                    BuiltInMethodKind::MathCbrt => x.cbrt(),
+                    BuiltInMethodKind::MathCeil => x.ceil(),
+                    BuiltInMethodKind::MathCos => x.cos(),
+                    BuiltInMethodKind::MathCosh => x.cosh(),
+                    BuiltInMethodKind::MathExp => x.exp(),
+                    BuiltInMethodKind::MathExpm1 => x.exp_m1(),
+                    BuiltInMethodKind::MathFloor => x.floor(),
+                    BuiltInMethodKind::MathLog => x.ln(),
+                    BuiltInMethodKind::MathLog10 => x.log10(),
+                    BuiltInMethodKind::MathLog1p => x.ln_1p(),
+                    BuiltInMethodKind::MathLog2 => x.log2(),
+                    BuiltInMethodKind::MathRound => js_round(x),
+                    BuiltInMethodKind::MathSign => {
+                        // `f64::signum` maps 0 to 1 and NaN stays NaN
+                        if x == 0.0 || x.is_nan() {
+                            x
+                        } else {
+                            x.signum()
+                        }
+                    }
+                    BuiltInMethodKind::MathSin => x.sin(),
+                    BuiltInMethodKind::MathSinh => x.sinh(),
+                    BuiltInMethodKind::MathSqrt => x.sqrt(),
+                    BuiltInMethodKind::MathTan => x.tan(),
+                    BuiltInMethodKind::MathTanh => x.tanh(),
+                    BuiltInMethodKind::MathTrunc => x.trunc(),
+                    method => {
+                        return Err(EvaluationError::TypeError(
+                            format!("{:?} is not a Math function.", method),
+                            None,
+                        ))
+                    }
+                })
+            }
+        };
+        Ok(number_value(result.unwrap_or(f64::NAN)))
+    }
+}
+
+/// `Math.round` rounds half-way cases towards +Infinity, so `Math.round(-2.5)` is -2.
+/// Negative numbers that round to zero give -0.
+fn js_round(x: f64) -> f64 {
+    let floor = x.floor();
+    let rounded = if x - floor >= 0.5 { floor + 1.0 } else { floor };
+    if rounded == 0.0 && x.is_sign_negative() {
+        -0.0
+    } else {
+        rounded
+    }
+}
+
+/// `Math.pow` and `**` share JavaScript's rules, where `1 ** NaN` and `1 ** Infinity`
+/// are NaN rather than 1.
+pub(super) fn js_pow(base: f64, exponent: f64) -> f64 {
+    if exponent.is_nan() || (base.abs() == 1.0 && exponent.is_infinite()) {
+        f64::NAN
+    } else {
+        base.powf(exponent)
+    }
+}
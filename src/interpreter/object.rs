@@ -0,0 +1,133 @@
+use super::{argument_values, check_arity, values_with_arity, Interpreter};
+use crate::{Argument, BuiltInMethodKind, EvaluationError};
+use crate::{Map, Value};
+
+pub(super) const OBJECT_FUNCTIONS: &[(&str, BuiltInMethodKind)] = &[
+    ("assign", BuiltInMethodKind::ObjectAssign),
+    ("entries", BuiltInMethodKind::ObjectEntries),
+    ("fromEntries", BuiltInMethodKind::ObjectFromEntries),
+    ("keys", BuiltInMethodKind::ObjectKeys),
+    ("values", BuiltInMethodKind::ObjectValues),
+];
+
+impl Interpreter<'_> {
+    /// Calls an `Object` static function.
+    ///
+    /// Keys are listed in the order of the underlying [`Map`]: sorted by default, or in
+    /// insertion order when the `preserve_order` feature is on.
+    pub(super) fn call_object_function(
+        &self,
+        method: BuiltInMethodKind,
+        args: Vec<Argument<'_, Value>>,
+    ) -> Result<Value, EvaluationError> {
+        match method {
+            BuiltInMethodKind::ObjectKeys => {
+                let args = values_with_arity(args, 1, 1)?;
+                let keys = self.own_entries(&args[0])?.into_iter().map(|(key, _)| key);
+                Ok(Value::Array(keys.map(Value::String).collect()))
+            }
+            BuiltInMethodKind::ObjectValues => {
+                let args = values_with_arity(args, 1, 1)?;
+                let values = self
+                    .own_entries(&args[0])?
+                    .into_iter()
+                    .map(|(_, value)| value);
+                Ok(Value::Array(values.collect()))
+            }
+            BuiltInMethodKind::ObjectEntries => {
+                let args = values_with_arity(args, 1, 1)?;
+                let entries = self
+                    .own_entries(&args[0])?
+                    .into_iter()
+                    .map(|(key, value)| Value::Array(vec![Value::String(key), value]));
+                Ok(Value::Array(entries.collect()))
+            }
+            BuiltInMethodKind::ObjectFromEntries => {
+                let args = values_with_arity(args, 1, 1)?;
+                let Value::Array(entries) = &args[0] else {
+                    return Err(EvaluationError::TypeError(
+                        format!("{} is not iterable", self.value_to_string(&args[0])),
+                        None,
+                    ));
+                };
+                let mut map = Map::new();
+                for entry in entries {
+                    let Value::Array(pair) = entry else {
+                        return Err(EvaluationError::TypeError(
+                            format!(
+                                "Iterator value {} is not an entry object",
+                                self.value_to_string(entry)
+                            ),
+                            None,
+                        ));
+                    };
+                    let key = pair.first().unwrap_or(&Value::Undefined);
+                    let value = pair.get(1).cloned().unwrap_or_default();
+                    map.insert(self.to_property_key(key), value);
+                }
+                Ok(Value::Object(map))
+            }
+            BuiltInMethodKind::ObjectAssign => {
+                check_arity(&args, 1, usize::MAX)?;
+                let mut args = argument_values(args)?.into_iter();
+                // Nothing is mutated: the result is a merged copy of the target
+                let mut target = match args.next() {
+                    Some(Value::Object(map)) => map,
+                    Some(other) => {
+                        return Err(EvaluationError::TypeError(
+                            format!(
+                                "Object.assign target must be an object, got {}",
+                                self.value_to_string(&other)
+                            ),
+                            None,
+                        ))
+                    }
+                    None => Map::new(),
+                };
+                for source in args {
+                    // Null and undefined sources are skipped
+                    if !source.is_nullish() {
+                        target.extend(self.own_entries(&source)?);
+                    }
+                }
+                Ok(Value::Object(target))
+            }
+            method => Err(EvaluationError::TypeError(
+                format!("{:?} is not an Object function.", method),
+                None,
+            )),
+        }
+    }
+
+    /// The own enumerable properties of a value, as `Object.entries` sees them: array
+    /// and string indices become string keys, and other primitives have none.
+    fn own_entries(&self, value: &Value) -> Result<Vec<(String, Value)>, EvaluationError> {
+        let entries = match value {
+            Value::Object(map) => map
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+            Value::Array(items) => items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| (i.to_string(), item.clone()))
+                .collect(),
+            Value::String(s) => s
+                .encode_utf16()
+                .enumerate()
+                .map(|(i, unit)| {
+                    let unit = Value::String(String::from_utf16_lossy(&[unit]));
+                    (i.to_string(), unit)
+                })
+                .collect(),
+            Value::Null | Value::Undefined => {
+                return Err(EvaluationError::TypeError(
+                    "Cannot convert undefined or null to object".to_string(),
+                    None,
+                ))
+            }
+            Value::Bool(_) | Value::Number(_) | Value::BigInt(_) | Value::Function(_) => Vec::new(),
+        };
+        Ok(entries)
+    }
+}
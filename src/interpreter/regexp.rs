@@ -0,0 +1,465 @@
+use super::{argument_value, check_arity, number_value, values_with_arity, Interpreter};
+use crate::ast::{Expr, ExprKind};
+use crate::{Argument, BuiltInMethodKind, CustomFuncError, EvaluationError, RegExp};
+use crate::{Map, Value};
+use regex::{Captures, Regex};
+
+impl Interpreter<'_> {
+    /// Evaluates `expr` to a regular expression if it is a regex literal or a `RegExp`
+    /// constructor call, returning `None` for any other expression.
+    pub(super) fn evaluate_regexp(&self, expr: &Expr) -> Result<Option<RegExp>, EvaluationError> {
+        let args = match &expr.kind {
+            ExprKind::RegExp(regexp) => return Ok(Some(regexp.clone())),
+            ExprKind::New { callee, args } if is_regexp_constructor(callee) => args,
+            // `RegExp(...)` without `new` builds one too, unless a custom function
+            // claims the name
+            ExprKind::Call { callee, args, .. }
+                if is_regexp_constructor(callee)
+                    && !self.custom_functions.contains_key("RegExp") =>
+            {
+                args
+            }
+            _ => return Ok(None),
+        };
+        if args.len() > 2 {
+            return Err(EvaluationError::CustomFunction(
+                CustomFuncError::ArityError {
+                    expected: 2,
+                    got: args.len(),
+                },
+                None,
+            ));
+        }
+
+        // `new RegExp(/a/g)` copies the pattern and, unless given new ones, the flags
+        let (source, inherited_flags) = match args.first() {
+            None => (String::new(), String::new()),
+            Some(arg) => match self.evaluate_argument(arg)? {
+                Argument::RegExp(regexp) => (regexp.source().to_string(), regexp.flags()),
+                arg => match argument_value(&arg)? {
+                    Value::Undefined => (String::new(), String::new()),
+                    value => (self.to_property_key(&value), String::new()),
+                },
+            },
+        };
+        let flags = match args.get(1) {
+            None => inherited_flags,
+            Some(arg) => match self.evaluate_node(arg)? {
+                Value::Undefined => inherited_flags,
+                value => self.to_property_key(&value),
+            },
+        };
+        self.compile_regexp(&source, &flags).map(Some)
+    }
+
+    /// Compiles a pattern built at run time, reusing an earlier compilation of the same
+    /// pattern and flags by this expression.
+    fn compile_regexp(&self, source: &str, flags: &str) -> Result<RegExp, EvaluationError> {
+        self.regexps
+            .get_or_compile(source, flags)
+            .map_err(|reason| {
+                EvaluationError::TypeError(
+                    format!(
+                        "Invalid regular expression: /{}/{}: {}",
+                        source, flags, reason
+                    ),
+                    None,
+                )
+            })
+    }
+
+    /// Calls `test`, `exec` or `toString` on a regular expression.
+    ///
+    /// Regular expressions are not stateful: `lastIndex` is always 0, so `exec` on a
+    /// global pattern returns the first match like any other.
+    pub(super) fn call_regexp_method(
+        &self,
+        regexp: &RegExp,
+        name: &str,
+        args: Vec<Argument<'_, Value>>,
+    ) -> Result<Value, EvaluationError> {
+        match name {
+            "test" => {
+                let args = values_with_arity(args, 1, 1)?;
+                Ok(Value::Bool(
+                    regexp.is_match(&self.to_property_key(&args[0])),
+                ))
+            }
+            "exec" => {
+                let args = values_with_arity(args, 1, 1)?;
+                let text = self.to_property_key(&args[0]);
+                Ok(regexp
+                    .captures_at(&text, 0)
+                    .map_or(Value::Null, |captures| match_array(&captures)))
+            }
+            "toString" => {
+                check_arity(&args, 0, 0)?;
+                Ok(Value::String(regexp.to_string()))
+            }
+            _ => Err(EvaluationError::TypeError(
+                format!("{}.{} is not a function.", regexp, name),
+                None,
+            )),
+        }
+    }
+
+    /// Calls a string method that takes a regular expression: `match`, `matchAll` and
+    /// `search` always do, and `split`, `replace` and `replaceAll` when given one.
+    ///
+    /// Match positions are reported in UTF-16 code units, like other string positions.
+    pub(super) fn call_string_regexp_method(
+        &self,
+        s: String,
+        method: BuiltInMethodKind,
+        args: Vec<Argument<'_, Value>>,
+    ) -> Result<Value, EvaluationError> {
+        match method {
+            BuiltInMethodKind::StringMatch => {
+                check_arity(&args, 0, 1)?;
+                let regexp = self.regexp_argument(args.first(), "")?;
+                if !regexp.regex_flags().global {
+                    return Ok(regexp
+                        .captures_at(&s, 0)
+                        .map_or(Value::Null, |captures| match_array(&captures)));
+                }
+                let matches: Vec<Value> = all_captures(&regexp, &s)
+                    .iter()
+                    .map(|captures| Value::String(captures[0].to_string()))
+                    .collect();
+                Ok(if matches.is_empty() {
+                    Value::Null
+                } else {
+                    Value::Array(matches)
+                })
+            }
+            BuiltInMethodKind::StringMatchAll => {
+                check_arity(&args, 0, 1)?;
+                let regexp = self.regexp_argument(args.first(), "g")?;
+                if !regexp.regex_flags().global {
+                    return Err(EvaluationError::TypeError(
+                        format!(
+                            "matchAll must be called with a global RegExp, got {}",
+                            regexp
+                        ),
+                        None,
+                    ));
+                }
+                // An array of `exec` results stands in for the iterator
+                Ok(Value::Array(
+                    all_captures(&regexp, &s).iter().map(match_array).collect(),
+                ))
+            }
+            BuiltInMethodKind::StringSearch => {
+                check_arity(&args, 0, 1)?;
+                let regexp = self.regexp_argument(args.first(), "")?;
+                let position = regexp
+                    .captures_at(&s, 0)
+                    .map(|captures| utf16_offset(&s, captures.get(0).unwrap().start()));
+                Ok(number_value(position.unwrap_or(-1.0)))
+            }
+            BuiltInMethodKind::StringSplit => {
+                check_arity(&args, 1, 2)?;
+                let regexp = self.regexp_argument(args.first(), "")?;
+                let limit = match args.get(1).map(argument_value).transpose()? {
+                    None | Some(Value::Undefined) => u32::MAX,
+                    Some(limit) => self.to_uint32(&limit)?,
+                } as usize;
+                Ok(Value::Array(split_regexp(&s, regexp.regex(), limit)))
+            }
+            BuiltInMethodKind::StringReplace | BuiltInMethodKind::StringReplaceAll => {
+                check_arity(&args, 2, 2)?;
+                let regexp = self.regexp_argument(args.first(), "")?;
+                let global = regexp.regex_flags().global;
+                if method == BuiltInMethodKind::StringReplaceAll && !global {
+                    return Err(EvaluationError::TypeError(
+                        format!(
+                            "replaceAll must be called with a global RegExp, got {}",
+                            regexp
+                        ),
+                        None,
+                    ));
+                }
+                let matches = if global {
+                    all_captures(&regexp, &s)
+                } else {
+                    regexp.captures_at(&s, 0).into_iter().collect()
+                };
+
+                let mut result = String::with_capacity(s.len());
+                let mut last = 0;
+                for captures in &matches {
+                    let whole = captures.get(0).unwrap();
+                    result.push_str(&s[last..whole.start()]);
+                    match &args[1] {
+                        Argument::Lambda(replacer) => {
+                            // The replacer receives (match, ...groups, offset, string),
+                            // followed by the named groups if there are any
+                            let mut call_args = match_values(captures);
+                            call_args.push(number_value(utf16_offset(&s, whole.start())));
+                            call_args.push(Value::String(s.clone()));
+                            call_args.extend(named_groups(captures, regexp.regex()));
+                            let replacement = replacer.call_value(&call_args)?;
+                            result.push_str(&self.to_property_key(&replacement));
+                        }
+                        template => {
+                            let template = self.to_property_key(&argument_value(template)?);
+                            expand_replacement(
+                                &mut result,
+                                &template,
+                                &s,
+                                captures,
+                                regexp.regex(),
+                            );
+                        }
+                    }
+                    last = whole.end();
+                }
+                result.push_str(&s[last..]);
+                Ok(Value::String(result))
+            }
+            method => Err(EvaluationError::TypeError(
+                format!("{:?} does not take a regular expression.", method),
+                None,
+            )),
+        }
+    }
+
+    /// The regular expression argument of `match`, `matchAll` and `search`. Other values
+    /// are converted to strings and compiled as patterns, as in JavaScript.
+    fn regexp_argument(
+        &self,
+        arg: Option<&Argument<'_, Value>>,
+        flags: &str,
+    ) -> Result<RegExp, EvaluationError> {
+        let source = match arg {
+            Some(Argument::RegExp(regexp)) => return Ok(regexp.clone()),
+            Some(arg) => match argument_value(arg)? {
+                Value::Undefined => String::new(),
+                value => self.to_property_key(&value),
+            },
+            None => String::new(),
+        };
+        self.compile_regexp(&source, flags)
+    }
+}
+
+/// The error for a regular expression used where a value is needed. Like arrow
+/// functions, they only exist as method receivers and arguments.
+pub(super) fn regexp_value_error(regexp: &RegExp) -> EvaluationError {
+    EvaluationError::TypeError(
+        format!(
+            "Regular expression {} can only be used to call a method or as an argument",
+            regexp
+        ),
+        None,
+    )
+}
+
+/// Reads a property of a regular expression, such as `source` or `global`.
+pub(super) fn regexp_property(regexp: &RegExp, name: &str) -> Value {
+    let flags = regexp.regex_flags();
+    match name {
+        "source" => Value::String(regexp.source().to_string()),
+        "flags" => Value::String(regexp.flags()),
+        "global" => Value::Bool(flags.global),
+        "ignoreCase" => Value::Bool(flags.ignore_case),
+        "multiline" => Value::Bool(flags.multiline),
+        "dotAll" => Value::Bool(flags.dot_all),
+        "unicode" => Value::Bool(flags.unicode),
+        "sticky" => Value::Bool(flags.sticky),
+        "lastIndex" => number_value(0.0),
+        _ => Value::Undefined,
+    }
+}
+
+fn is_regexp_constructor(callee: &Expr) -> bool {
+    matches!(&callee.kind, ExprKind::Identifier(name) if name == "RegExp")
+}
+
+/// Every match of a global regular expression, found the way repeated `exec` calls
+/// would: an empty match moves the search on by one character.
+fn all_captures<'t>(regexp: &RegExp, text: &'t str) -> Vec<Captures<'t>> {
+    let mut matches = Vec::new();
+    let mut position = 0;
+    while position <= text.len() {
+        let Some(captures) = regexp.captures_at(text, position) else {
+            break;
+        };
+        let whole = captures.get(0).unwrap();
+        position = if whole.is_empty() {
+            whole.end() + next_char_len(text, whole.end())
+        } else {
+            whole.end()
+        };
+        matches.push(captures);
+    }
+    matches
+}
+
+/// `String.prototype.split` with a regular expression separator. Captured groups are
+/// spliced into the result, and an empty match never splits at the position where the
+/// previous piece ended.
+fn split_regexp(s: &str, regex: &Regex, limit: usize) -> Vec<Value> {
+    let mut parts = Vec::new();
+    if limit == 0 {
+        return parts;
+    }
+    if s.is_empty() {
+        if !regex.is_match(s) {
+            parts.push(Value::String(String::new()));
+        }
+        return parts;
+    }
+
+    let (mut piece_start, mut position) = (0, 0);
+    while position < s.len() {
+        let Some(captures) = regex.captures_at(s, position) else {
+            break;
+        };
+        let whole = captures.get(0).unwrap();
+        if whole.start() >= s.len() {
+            break;
+        }
+        position = whole.start();
+        if whole.end() == piece_start {
+            position += next_char_len(s, position);
+            continue;
+        }
+
+        parts.push(Value::String(s[piece_start..position].to_string()));
+        parts.extend(match_values(&captures).into_iter().skip(1));
+        if parts.len() >= limit {
+            parts.truncate(limit);
+            return parts;
+        }
+        piece_start = whole.end();
+        position = piece_start;
+    }
+    parts.push(Value::String(s[piece_start..].to_string()));
+    parts.truncate(limit);
+    parts
+}
+
+/// Expands a replacement template: `$$`, `$&`, `` $` ``, `$'`, the numbered groups
+/// `$1` to `$99` and, if the pattern names any groups, `$<name>`. Anything else is
+/// copied as is.
+fn expand_replacement(
+    result: &mut String,
+    template: &str,
+    text: &str,
+    captures: &Captures<'_>,
+    regex: &Regex,
+) {
+    let whole = captures.get(0).unwrap();
+    let group_count = captures.len() - 1;
+    let is_group = |n: usize| (1..=group_count).contains(&n);
+    let push_group = |result: &mut String, n: usize| {
+        if let Some(group) = captures.get(n) {
+            result.push_str(group.as_str());
+        }
+    };
+
+    let mut rest = template;
+    while let Some(dollar) = rest.find('$') {
+        result.push_str(&rest[..dollar]);
+        let after = &rest[dollar + 1..];
+        let mut chars = after.chars();
+        let consumed = match chars.next() {
+            Some('$') => {
+                result.push('$');
+                1
+            }
+            Some('&') => {
+                result.push_str(whole.as_str());
+                1
+            }
+            Some('`') => {
+                result.push_str(&text[..whole.start()]);
+                1
+            }
+            Some('\'') => {
+                result.push_str(&text[whole.end()..]);
+                1
+            }
+            Some(digit) if digit.is_ascii_digit() => {
+                // Two digits win when they name a group, so `$10` is group 10 only if
+                // there are at least ten groups
+                let first = digit.to_digit(10).unwrap() as usize;
+                let both = chars
+                    .next()
+                    .and_then(|c| c.to_digit(10))
+                    .map(|second| first * 10 + second as usize);
+                match both.filter(|&n| is_group(n)) {
+                    Some(n) => {
+                        push_group(result, n);
+                        2
+                    }
+                    None if is_group(first) => {
+                        push_group(result, first);
+                        1
+                    }
+                    None => {
+                        result.push('$');
+                        0
+                    }
+                }
+            }
+            Some('<') if regex.capture_names().flatten().next().is_some() => {
+                match after.find('>') {
+                    Some(close) => {
+                        if let Some(group) = captures.name(&after[1..close]) {
+                            result.push_str(group.as_str());
+                        }
+                        close + 1
+                    }
+                    None => {
+                        result.push('$');
+                        0
+                    }
+                }
+            }
+            _ => {
+                result.push('$');
+                0
+            }
+        };
+        rest = &after[consumed..];
+    }
+    result.push_str(rest);
+}
+
+/// The matched text followed by each group, with undefined for groups that did not
+/// take part in the match.
+fn match_values(captures: &Captures<'_>) -> Vec<Value> {
+    captures
+        .iter()
+        .map(|group| group.map_or(Value::Undefined, |m| Value::String(m.as_str().to_string())))
+        .collect()
+}
+
+/// An `exec` result. Arrays cannot carry the `index` and `groups` properties, so only
+/// the matched text and the groups are returned.
+fn match_array(captures: &Captures<'_>) -> Value {
+    Value::Array(match_values(captures))
+}
+
+/// The named groups of a match as an object, or `None` if the pattern names no groups.
+fn named_groups(captures: &Captures<'_>, regex: &Regex) -> Option<Value> {
+    let mut groups = Map::new();
+    for name in regex.capture_names().flatten() {
+        let value = captures
+            .name(name)
+            .map_or(Value::Undefined, |m| Value::String(m.as_str().to_string()));
+        groups.insert(name.to_string(), value);
+    }
+    (!groups.is_empty()).then_some(Value::Object(groups))
+}
+
+/// Converts a byte offset in `s` into a UTF-16 position, as JavaScript reports them.
+fn utf16_offset(s: &str, byte_offset: usize) -> f64 {
+    s[..byte_offset].encode_utf16().count() as f64
+}
+
+fn next_char_len(s: &str, byte_offset: usize) -> usize {
+    s[byte_offset..].chars().next().map_or(1, char::len_utf8)
+}
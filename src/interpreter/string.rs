@@ -0,0 +1,414 @@
+use super::{
+    argument_value, check_arity, integer_or_infinity, number_value, values_with_arity, Interpreter,
+};
+use crate::Value;
+use crate::{Argument, BuiltInMethodKind, EvaluationError};
+use std::cmp::Ordering;
+
+/// The longest string (in UTF-16 code units) `repeat`, `padStart` and `padEnd` will
+/// build, matching V8's limit.
+const MAX_STRING_LENGTH: f64 = ((1 << 29) - 24) as f64;
+
+/// Maps a `String.prototype` method name to its built-in kind.
+pub(super) fn string_method(name: &str) -> Option<BuiltInMethodKind> {
+    let method = match name {
+        "startsWith" => BuiltInMethodKind::StringStartsWith,
+        "endsWith" => BuiltInMethodKind::StringEndsWith,
+        "includes" => BuiltInMethodKind::StringIncludes,
+        "indexOf" => BuiltInMethodKind::StringIndexOf,
+        "slice" => BuiltInMethodKind::StringSlice,
+        "substring" => BuiltInMethodKind::StringSubstring,
+        "toLowerCase" => BuiltInMethodKind::StringToLowerCase,
+        "toUpperCase" => BuiltInMethodKind::StringToUpperCase,
+        "trim" => BuiltInMethodKind::StringTrim,
+        "trimStart" => BuiltInMethodKind::StringTrimStart,
+        "trimEnd" => BuiltInMethodKind::StringTrimEnd,
+        "split" => BuiltInMethodKind::StringSplit,
+        "replace" => BuiltInMethodKind::StringReplace,
+        "replaceAll" => BuiltInMethodKind::StringReplaceAll,
+        "padStart" => BuiltInMethodKind::StringPadStart,
+        "padEnd" => BuiltInMethodKind::StringPadEnd,
+        "repeat" => BuiltInMethodKind::StringRepeat,
+        "charAt" => BuiltInMethodKind::StringCharAt,
+        "at" => BuiltInMethodKind::StringAt,
+        "localeCompare" => BuiltInMethodKind::StringLocaleCompare,
+        "match" => BuiltInMethodKind::StringMatch,
+        "matchAll" => BuiltInMethodKind::StringMatchAll,
+        "search" => BuiltInMethodKind::StringSearch,
+        _ => return None,
+    };
+    Some(method)
+}
+
+/// The UTF-16 length of `s`, which is what JavaScript's `length` reports.
+pub(super) fn string_length(s: &str) -> usize {
+    s.encode_utf16().count()
+}
+
+impl Interpreter<'_> {
+    /// Calls a `String.prototype` method on `s`.
+    ///
+    /// Positions and lengths are counted in UTF-16 code units, as in JavaScript, so
+    /// `'😀a'.indexOf('a')` is 2.
+    pub(super) fn call_string_method(
+        &self,
+        s: String,
+        method: BuiltInMethodKind,
+        args: Vec<Argument<'_, Value>>,
+    ) -> Result<Value, EvaluationError> {
+        match method {
+            BuiltInMethodKind::StringMatch
+            | BuiltInMethodKind::StringMatchAll
+            | BuiltInMethodKind::StringSearch => {
+                return self.call_string_regexp_method(s, method, args)
+            }
+            BuiltInMethodKind::StringSplit
+            | BuiltInMethodKind::StringReplace
+            | BuiltInMethodKind::StringReplaceAll
+                if matches!(args.first(), Some(Argument::RegExp(_))) =>
+            {
+                return self.call_string_regexp_method(s, method, args)
+            }
+            _ => {}
+        }
+
+        let units: Vec<u16> = s.encode_utf16().collect();
+        let len = units.len();
+
+        match method {
+            BuiltInMethodKind::StringStartsWith => {
+                let args = values_with_arity(args, 1, 2)?;
+                let search = self.string_units(&args[0]);
+                let start = self.clamped_position(args.get(1), len, 0)?;
+                Ok(Value::Bool(units[start..].starts_with(&search)))
+            }
+            BuiltInMethodKind::StringEndsWith => {
+                let args = values_with_arity(args, 1, 2)?;
+                let search = self.string_units(&args[0]);
+                let end = self.clamped_position(args.get(1), len, len)?;
+                Ok(Value::Bool(units[..end].ends_with(&search)))
+            }
+            BuiltInMethodKind::StringIncludes => {
+                let args = values_with_arity(args, 1, 2)?;
+                let search = self.string_units(&args[0]);
+                let start = self.clamped_position(args.get(1), len, 0)?;
+                Ok(Value::Bool(find_units(&units, &search, start).is_some()))
+            }
+            BuiltInMethodKind::StringIndexOf => {
+                let args = values_with_arity(args, 1, 2)?;
+                let search = self.string_units(&args[0]);
+                let start = self.clamped_position(args.get(1), len, 0)?;
+                let position = find_units(&units, &search, start).map(|i| i as f64);
+                Ok(number_value(position.unwrap_or(-1.0)))
+            }
+            BuiltInMethodKind::StringSlice => {
+                let args = values_with_arity(args, 0, 2)?;
+                let start = self.relative_index(args.first(), len, 0)?;
+                let end = self.relative_index(args.get(1), len, len)?;
+                Ok(string_value(units.get(start..end).unwrap_or_default()))
+            }
+            BuiltInMethodKind::StringSubstring => {
+                let args = values_with_arity(args, 0, 2)?;
+                // Unlike `slice`, negative positions clamp to 0 and the bounds are swapped
+                // when start is past end
+                let start = self.clamped_position(args.first(), len, 0)?;
+                let end = self.clamped_position(args.get(1), len, len)?;
+                Ok(string_value(&units[start.min(end)..start.max(end)]))
+            }
+            BuiltInMethodKind::StringToLowerCase => {
+                check_arity(&args, 0, 0)?;
+                Ok(Value::String(s.to_lowercase()))
+            }
+            BuiltInMethodKind::StringToUpperCase => {
+                check_arity(&args, 0, 0)?;
+                Ok(Value::String(s.to_uppercase()))
+            }
+            BuiltInMethodKind::StringTrim => {
+                check_arity(&args, 0, 0)?;
+                Ok(Value::String(s.trim_matches(is_js_whitespace).to_string()))
+            }
+            BuiltInMethodKind::StringTrimStart => {
+                check_arity(&args, 0, 0)?;
+                Ok(Value::String(
+                    s.trim_start_matches(is_js_whitespace).to_string(),
+                ))
+            }
+            BuiltInMethodKind::StringTrimEnd => {
+                check_arity(&args, 0, 0)?;
+                Ok(Value::String(
+                    s.trim_end_matches(is_js_whitespace).to_string(),
+                ))
+            }
+            BuiltInMethodKind::StringSplit => {
+                let args = values_with_arity(args, 0, 2)?;
+                let limit = match args.get(1) {
+                    None | Some(Value::Undefined) => u32::MAX,
+                    Some(limit) => self.to_uint32(limit)?,
+                } as usize;
+                let separator = match args.first() {
+                    None | Some(Value::Undefined) => {
+                        // Without a separator the result is the whole string
+                        let parts = if limit == 0 {
+                            vec![]
+                        } else {
+                            vec![Value::String(s)]
+                        };
+                        return Ok(Value::Array(parts));
+                    }
+                    Some(separator) => self.string_units(separator),
+                };
+                Ok(Value::Array(
+                    split_units(&units, &separator)
+                        .into_iter()
+                        .take(limit)
+                        .map(string_value)
+                        .collect(),
+                ))
+            }
+            BuiltInMethodKind::StringReplace | BuiltInMethodKind::StringReplaceAll => {
+                check_arity(&args, 2, 2)?;
+                let search = self.string_units(&argument_value(&args[0])?);
+                let positions = if method == BuiltInMethodKind::StringReplace {
+                    find_units(&units, &search, 0).into_iter().collect()
+                } else {
+                    find_all_units(&units, &search)
+                };
+
+                let mut result = Vec::with_capacity(len);
+                let mut last = 0;
+                for position in positions {
+                    result.extend_from_slice(&units[last..position]);
+                    let end = position + search.len();
+                    match &args[1] {
+                        Argument::Lambda(replacer) => {
+                            // The replacer receives (match, offset, string)
+                            let replacement = replacer.call_value(&[
+                                string_value(&search),
+                                number_value(position as f64),
+                                Value::String(s.clone()),
+                            ])?;
+                            result.extend(self.to_property_key(&replacement).encode_utf16());
+                        }
+                        template => {
+                            let template = self.string_units(&argument_value(template)?);
+                            expand_replacement(&mut result, &template, &units, position, end);
+                        }
+                    }
+                    last = end;
+                }
+                result.extend_from_slice(&units[last..]);
+                Ok(string_value(&result))
+            }
+            BuiltInMethodKind::StringPadStart | BuiltInMethodKind::StringPadEnd => {
+                let args = values_with_arity(args, 1, 2)?;
+                let target = integer_or_infinity(self.to_number(&args[0])?);
+                let filler = match args.get(1) {
+                    None | Some(Value::Undefined) => vec![b' ' as u16],
+                    Some(filler) => self.string_units(filler),
+                };
+                if target <= len as f64 || filler.is_empty() {
+                    return Ok(Value::String(s));
+                }
+                check_string_length(target)?;
+                let padding: Vec<u16> = filler
+                    .iter()
+                    .copied()
+                    .cycle()
+                    .take(target as usize - len)
+                    .collect();
+                let padded = if method == BuiltInMethodKind::StringPadStart {
+                    [padding, units].concat()
+                } else {
+                    [units, padding].concat()
+                };
+                Ok(string_value(&padded))
+            }
+            BuiltInMethodKind::StringRepeat => {
+                let args = values_with_arity(args, 1, 1)?;
+                let count = integer_or_infinity(self.to_number(&args[0])?);
+                if count < 0.0 || count.is_infinite() {
+                    return Err(EvaluationError::TypeError(
+                        format!("Invalid count value: {}", self.to_property_key(&args[0])),
+                        None,
+                    ));
+                }
+                check_string_length(len as f64 * count)?;
+                Ok(Value::String(s.repeat(count as usize)))
+            }
+            BuiltInMethodKind::StringCharAt => {
+                let args = values_with_arity(args, 0, 1)?;
+                let index = match args.first() {
+                    None | Some(Value::Undefined) => 0.0,
+                    Some(index) => integer_or_infinity(self.to_number(index)?),
+                };
+                Ok(if index >= 0.0 && index < len as f64 {
+                    string_value(&units[index as usize..index as usize + 1])
+                } else {
+                    Value::String(String::new())
+                })
+            }
+            BuiltInMethodKind::StringAt => {
+                let args = values_with_arity(args, 0, 1)?;
+                // A missing index is undefined, which converts to 0
+                let index = args.first().unwrap_or(&Value::Undefined);
+                let index = integer_or_infinity(self.to_number(index)?);
+                let index = if index < 0.0 {
+                    len as f64 + index
+                } else {
+                    index
+                };
+                Ok(if index >= 0.0 && index < len as f64 {
+                    string_value(&units[index as usize..index as usize + 1])
+                } else {
+                    Value::Undefined
+                })
+            }
+            BuiltInMethodKind::StringLocaleCompare => {
+                // `locales` and `options` are accepted for compatibility but ignored
+                let args = values_with_arity(args, 1, 3)?;
+                let other = self.to_property_key(&args[0]);
+                let ordering = match locale_compare(&s, &other) {
+                    Ordering::Less => -1.0,
+                    Ordering::Equal => 0.0,
+                    Ordering::Greater => 1.0,
+                };
+                Ok(number_value(ordering))
+            }
+            method => Err(EvaluationError::TypeError(
+                format!("{:?} method called on a string.", method),
+                None,
+            )),
+        }
+    }
+
+    /// Converts a value to a string the way JavaScript's `String(value)` does, as UTF-16
+    /// code units.
+    fn string_units(&self, value: &Value) -> Vec<u16> {
+        self.to_property_key(value).encode_utf16().collect()
+    }
+
+    /// Resolves a position argument such as `startsWith`'s `position`: negative values
+    /// clamp to 0 and large ones to `len`.
+    fn clamped_position(
+        &self,
+        value: Option<&Value>,
+        len: usize,
+        default: usize,
+    ) -> Result<usize, EvaluationError> {
+        match value {
+            None | Some(Value::Undefined) => Ok(default),
+            Some(value) => {
+                let position = integer_or_infinity(self.to_number(value)?);
+                Ok(position.clamp(0.0, len as f64) as usize)
+            }
+        }
+    }
+}
+
+fn string_value(units: &[u16]) -> Value {
+    Value::String(String::from_utf16_lossy(units))
+}
+
+/// The characters JavaScript's `trim` removes: `WhiteSpace` and `LineTerminator`.
+pub(super) fn is_js_whitespace(c: char) -> bool {
+    (c.is_whitespace() && c != '\u{85}') || c == '\u{feff}'
+}
+
+/// Returns the first position at or after `start` where `needle` occurs.
+fn find_units(haystack: &[u16], needle: &[u16], start: usize) -> Option<usize> {
+    if needle.is_empty() {
+        return (start <= haystack.len()).then_some(start);
+    }
+    haystack
+        .get(start..)?
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|offset| start + offset)
+}
+
+/// Returns every non-overlapping position of `needle`. An empty needle matches between
+/// every code unit, as in `'ab'.replaceAll('', '-')`.
+fn find_all_units(haystack: &[u16], needle: &[u16]) -> Vec<usize> {
+    let mut positions = Vec::new();
+    let mut start = 0;
+    while let Some(position) = find_units(haystack, needle, start) {
+        positions.push(position);
+        start = position + needle.len().max(1);
+    }
+    positions
+}
+
+fn split_units<'s>(units: &'s [u16], separator: &[u16]) -> Vec<&'s [u16]> {
+    if separator.is_empty() {
+        // An empty separator splits into code units, and `''.split('')` is empty
+        return units.chunks(1).collect();
+    }
+    let mut parts = Vec::new();
+    let mut last = 0;
+    for position in find_all_units(units, separator) {
+        parts.push(&units[last..position]);
+        last = position + separator.len();
+    }
+    parts.push(&units[last..]);
+    parts
+}
+
+/// Expands the `$$`, `$&`, `` $` `` and `$'` patterns of a replacement string. Any other
+/// `$` is copied as is.
+fn expand_replacement(
+    result: &mut Vec<u16>,
+    template: &[u16],
+    units: &[u16],
+    start: usize,
+    end: usize,
+) {
+    let mut i = 0;
+    while i < template.len() {
+        let unit = template[i];
+        let next = template.get(i + 1).copied();
+        if unit == b'$' as u16 {
+            match next.and_then(|n| char::from_u32(n as u32)) {
+                Some('$') => result.push(b'$' as u16),
+                Some('&') => result.extend_from_slice(&units[start..end]),
+                Some('`') => result.extend_from_slice(&units[..start]),
+                Some('\'') => result.extend_from_slice(&units[end..]),
+                _ => {
+                    result.push(unit);
+                    i += 1;
+                    continue;
+                }
+            }
+            i += 2;
+        } else {
+            result.push(unit);
+            i += 1;
+        }
+    }
+}
+
+fn check_string_length(length: f64) -> Result<(), EvaluationError> {
+    if length > MAX_STRING_LENGTH {
+        return Err(EvaluationError::TypeError(
+            "Invalid string length".to_string(),
+            None,
+        ));
+    }
+    Ok(())
+}
+
+/// An approximation of `localeCompare` without locale data: strings are compared
+/// case-insensitively first, then lowercase sorts before uppercase, and finally by code
+/// unit.
+fn locale_compare(a: &str, b: &str) -> Ordering {
+    a.to_lowercase()
+        .cmp(&b.to_lowercase())
+        .then_with(|| {
+            // ICU's default collation puts lowercase first, the reverse of code unit order
+            a.chars()
+                .zip(b.chars())
+                .find(|(x, y)| x != y)
+                .map(|(x, y)| y.is_lowercase().cmp(&x.is_lowercase()).then(x.cmp(&y)))
+                .unwrap_or(Ordering::Equal)
+        })
+        .then_with(|| a.encode_utf16().cmp(b.encode_utf16()))
+}
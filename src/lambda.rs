@@ -0,0 +1,173 @@
+use crate::ast::Expr;
+use crate::interpreter::{Interpreter, Scope};
+use crate::{EvaluationError, NonJsonPolicy, RegExp, Value};
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+/// An argument passed to [`CustomFunction::call_with_lambdas`](crate::CustomFunction::call_with_lambdas).
+///
+/// Custom functions receive values as `serde_json::Value`; built-in methods use the
+/// same type with [`Value`].
+#[derive(Debug)]
+pub enum Argument<'a, V = serde_json::Value> {
+    Value(V),
+    Lambda(Lambda<'a>),
+    /// A regular expression literal or `new RegExp(...)`.
+    RegExp(RegExp),
+}
+
+impl<'a, V> Argument<'a, V> {
+    /// Returns the argument's value, or `None` if it is an arrow function or regular
+    /// expression.
+    pub fn as_value(&self) -> Option<&V> {
+        match self {
+            Argument::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the arrow function, or `None` for any other argument.
+    pub fn as_lambda(&self) -> Option<&Lambda<'a>> {
+        match self {
+            Argument::Lambda(lambda) => Some(lambda),
+            _ => None,
+        }
+    }
+
+    /// Returns the regular expression, or `None` for any other argument.
+    pub fn as_regexp(&self) -> Option<&RegExp> {
+        match self {
+            Argument::RegExp(regexp) => Some(regexp),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Argument<'a, Value> {
+    /// Converts the value of an argument to JSON for a custom function.
+    pub(crate) fn into_json(self, policy: NonJsonPolicy) -> Result<Argument<'a>, EvaluationError> {
+        Ok(match self {
+            Argument::Value(value) => Argument::Value(value.to_json(policy)?),
+            Argument::Lambda(lambda) => Argument::Lambda(lambda),
+            Argument::RegExp(regexp) => Argument::RegExp(regexp),
+        })
+    }
+}
+
+/// An arrow function such as `x => x * 2`, the value an arrow function expression
+/// evaluates to.
+///
+/// Functions can be stored in arrays and objects, chosen with `?:` and called like any
+/// other function, such as `[x => x * 2][0](3)`. They remember the variables that were
+/// in scope where they were written, including the parameters of any enclosing arrow
+/// functions. Two functions are only equal if they come from the same evaluation of the
+/// same arrow function.
+#[derive(Clone)]
+pub struct Function(Arc<Closure>);
+
+struct Closure {
+    params: Arc<[String]>,
+    body: Arc<Expr>,
+    scope: Option<Arc<Scope>>,
+    /// The arrow function as written, which is also its string conversion.
+    source: String,
+}
+
+impl Function {
+    pub(crate) fn new(
+        params: Arc<[String]>,
+        body: Arc<Expr>,
+        scope: Option<Arc<Scope>>,
+        source: String,
+    ) -> Self {
+        Function(Arc::new(Closure {
+            params,
+            body,
+            scope,
+            source,
+        }))
+    }
+
+    /// The number of declared parameters.
+    pub fn arity(&self) -> usize {
+        self.0.params.len()
+    }
+
+    /// The source text of the arrow function, such as `x => x * 2`.
+    pub fn source(&self) -> &str {
+        &self.0.source
+    }
+}
+
+impl PartialEq for Function {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for Function {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Function").field(&self.0.source).finish()
+    }
+}
+
+/// A [`Function`] passed as an argument to a built-in method or custom function, ready
+/// to be called with the evaluator's functions and settings.
+pub struct Lambda<'a> {
+    interpreter: &'a Interpreter<'a>,
+    function: Function,
+}
+
+impl<'a> Lambda<'a> {
+    pub(crate) fn new(interpreter: &'a Interpreter<'a>, function: Function) -> Self {
+        Lambda {
+            interpreter,
+            function,
+        }
+    }
+
+    /// The function being called.
+    pub fn function(&self) -> &Function {
+        &self.function
+    }
+
+    /// The number of declared parameters.
+    pub fn arity(&self) -> usize {
+        self.function.arity()
+    }
+
+    /// Calls the function. As in JavaScript, missing arguments are undefined and
+    /// extra arguments are ignored.
+    ///
+    /// The result is converted to JSON with the evaluator's
+    /// [`NonJsonPolicy`](crate::NonJsonPolicy).
+    pub fn call(&self, args: &[serde_json::Value]) -> Result<serde_json::Value, EvaluationError> {
+        let args: Vec<Value> = args
+            .iter()
+            .map(|arg| self.interpreter.value_from_json(Cow::Borrowed(arg)))
+            .collect();
+        self.call_value(&args)?
+            .to_json(self.interpreter.non_json_policy())
+    }
+
+    pub(crate) fn call_value(&self, args: &[Value]) -> Result<Value, EvaluationError> {
+        let closure = &self.function.0;
+        let values = (0..closure.params.len())
+            .map(|i| args.get(i).cloned().unwrap_or_default())
+            .collect();
+        let scope = Scope::new(closure.params.clone(), values, closure.scope.clone());
+
+        self.interpreter
+            .with_scope(Arc::new(scope))
+            .evaluate_node(&closure.body)
+    }
+}
+
+impl fmt::Debug for Lambda<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Lambda")
+            .field("function", &self.function)
+            .finish_non_exhaustive()
+    }
+}
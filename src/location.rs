@@ -0,0 +1,112 @@
+use rslint_parser::{TextRange, TextSize};
+use std::fmt::{self, Write};
+
+/// Where in an expression's source an error occurred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    range: TextRange,
+    line: usize,
+    column: usize,
+}
+
+impl Location {
+    pub(crate) fn new(range: TextRange, source: &str) -> Self {
+        let start = floor_char_boundary(source, usize::from(range.start()));
+        let before = &source[..start];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Location {
+            range,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+
+    /// The byte range of the failing code, such as the sub-expression that raised a
+    /// type error.
+    pub fn range(&self) -> TextRange {
+        self.range
+    }
+
+    /// The line the range starts on, counting from 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column the range starts at, counting characters from 1.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// The range covering all of `source`, for errors that concern the whole expression.
+pub(crate) fn full_range(source: &str) -> TextRange {
+    TextRange::up_to(TextSize::from(source.len() as u32))
+}
+
+/// Writes `message` followed by the line of `source` that `location` points at, with
+/// the range underlined:
+///
+/// ```text
+/// error: Type error: Cannot read property 'length' of non-array/non-object value: null
+///  --> 1:1
+///   |
+/// 1 | user.tags.length > 0
+///   | ^^^^^^^^^^^^^^^^
+/// ```
+///
+/// A range that spans several lines is underlined up to the end of its first line.
+pub(crate) fn write_snippet(
+    out: &mut String,
+    message: &str,
+    location: Option<&Location>,
+    source: &str,
+) {
+    let _ = writeln!(out, "error: {}", message);
+    let Some(location) = location else {
+        return;
+    };
+    let Some(line_text) = source.split('\n').nth(location.line - 1) else {
+        return;
+    };
+    let line_text = line_text.strip_suffix('\r').unwrap_or(line_text);
+
+    let line_start = source
+        .split('\n')
+        .take(location.line - 1)
+        .map(|line| line.len() + 1)
+        .sum::<usize>();
+    let start = floor_char_boundary(source, usize::from(location.range.start())).max(line_start);
+    let end = floor_char_boundary(source, usize::from(location.range.end()))
+        .min(line_start + line_text.len())
+        .max(start);
+    let indent = source[line_start..start].chars().count();
+    let width = source[start..end].chars().count().max(1);
+
+    let gutter = " ".repeat(location.line.to_string().len());
+    let _ = writeln!(out, "{}--> {}", gutter, location);
+    let _ = writeln!(out, "{} |", gutter);
+    let _ = writeln!(out, "{} | {}", location.line, line_text);
+    let _ = writeln!(
+        out,
+        "{} | {}{}",
+        gutter,
+        " ".repeat(indent),
+        "^".repeat(width)
+    );
+}
+
+/// Clamps `index` to the length of `s` and moves it back to a character boundary, so
+/// that a location taken from another source cannot cause a panic.
+fn floor_char_boundary(s: &str, index: usize) -> usize {
+    let mut index = index.min(s.len());
+    while !s.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
@@ -0,0 +1,302 @@
+use regex::{Regex, RegexBuilder};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// The longest pattern, in characters, that a regular expression may have.
+const MAX_PATTERN_LENGTH: usize = 2048;
+
+/// Upper bound on the memory used by a compiled regular expression and by its lazy
+/// DFA. Together with the engine's linear-time matching this keeps untrusted patterns
+/// from exhausting memory or CPU.
+const MAX_COMPILED_SIZE: usize = 1 << 20;
+
+/// How deeply groups and repetitions may nest.
+const MAX_NESTING: u32 = 64;
+
+/// The most `new RegExp(...)` patterns a compiled expression keeps around.
+const MAX_CACHED_REGEXPS: usize = 64;
+
+/// What JavaScript's `\s` matches: `WhiteSpace` and `LineTerminator` characters.
+const JS_WHITESPACE: &str =
+    r"\t\n\x0B\f\r \xA0\x{1680}\x{2000}-\x{200A}\x{2028}\x{2029}\x{202F}\x{205F}\x{3000}\x{FEFF}";
+
+/// A compiled regular expression, written as a `/pattern/flags` literal or built with
+/// `new RegExp(pattern, flags)`.
+///
+/// Patterns run on the `regex` crate, which matches in linear time, so look-around
+/// assertions and backreferences are not supported.
+#[derive(Clone)]
+pub struct RegExp {
+    regex: Arc<Regex>,
+    source: Arc<str>,
+    flags: RegExpFlags,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct RegExpFlags {
+    pub(crate) global: bool,
+    pub(crate) ignore_case: bool,
+    pub(crate) multiline: bool,
+    pub(crate) dot_all: bool,
+    pub(crate) unicode: bool,
+    pub(crate) sticky: bool,
+}
+
+impl RegExp {
+    /// Compiles a JavaScript pattern, returning the reason it was rejected on failure.
+    pub(crate) fn new(source: &str, flags: &str) -> Result<Self, String> {
+        let flags = parse_flags(flags)?;
+        if source.chars().count() > MAX_PATTERN_LENGTH {
+            return Err(format!(
+                "pattern is longer than {} characters",
+                MAX_PATTERN_LENGTH
+            ));
+        }
+
+        let regex = RegexBuilder::new(&translate_pattern(source, flags.dot_all))
+            .case_insensitive(flags.ignore_case)
+            .multi_line(flags.multiline)
+            .dot_matches_new_line(flags.dot_all)
+            .size_limit(MAX_COMPILED_SIZE)
+            .dfa_size_limit(MAX_COMPILED_SIZE)
+            .nest_limit(MAX_NESTING)
+            .build()
+            .map_err(|err| match err {
+                regex::Error::CompiledTooBig(_) => "pattern is too large".to_string(),
+                // Syntax errors quote the translated pattern; only the reason is useful
+                err => err
+                    .to_string()
+                    .lines()
+                    .last()
+                    .unwrap_or_default()
+                    .trim_start_matches("error: ")
+                    .to_string(),
+            })?;
+
+        Ok(RegExp {
+            regex: Arc::new(regex),
+            source: Arc::from(if source.is_empty() { "(?:)" } else { source }),
+            flags,
+        })
+    }
+
+    /// The pattern as written, like JavaScript's `RegExp.prototype.source`.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// The flags in canonical order, like JavaScript's `RegExp.prototype.flags`.
+    pub fn flags(&self) -> String {
+        let flags = self.flags;
+        [
+            (flags.global, 'g'),
+            (flags.ignore_case, 'i'),
+            (flags.multiline, 'm'),
+            (flags.dot_all, 's'),
+            (flags.unicode, 'u'),
+            (flags.sticky, 'y'),
+        ]
+        .iter()
+        .filter(|(set, _)| *set)
+        .map(|(_, flag)| flag)
+        .collect()
+    }
+
+    /// Whether the pattern matches anywhere in `text` (or at its start for sticky
+    /// patterns).
+    pub fn is_match(&self, text: &str) -> bool {
+        self.captures_at(text, 0).is_some()
+    }
+
+    pub(crate) fn regex_flags(&self) -> RegExpFlags {
+        self.flags
+    }
+
+    pub(crate) fn regex(&self) -> &Regex {
+        &self.regex
+    }
+
+    /// Matches starting the search at byte offset `start`. A sticky pattern only
+    /// matches exactly at `start`.
+    pub(crate) fn captures_at<'t>(
+        &self,
+        text: &'t str,
+        start: usize,
+    ) -> Option<regex::Captures<'t>> {
+        self.regex
+            .captures_at(text, start)
+            .filter(|captures| !self.flags.sticky || captures.get(0).unwrap().start() == start)
+    }
+}
+
+impl PartialEq for RegExp {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source && self.flags == other.flags
+    }
+}
+
+impl fmt::Display for RegExp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "/{}/{}", self.source, self.flags())
+    }
+}
+
+impl fmt::Debug for RegExp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RegExp({})", self)
+    }
+}
+
+fn parse_flags(flags: &str) -> Result<RegExpFlags, String> {
+    let mut parsed = RegExpFlags::default();
+    for flag in flags.chars() {
+        let slot = match flag {
+            'g' => &mut parsed.global,
+            'i' => &mut parsed.ignore_case,
+            'm' => &mut parsed.multiline,
+            's' => &mut parsed.dot_all,
+            'u' => &mut parsed.unicode,
+            'y' => &mut parsed.sticky,
+            _ => return Err(format!("invalid flags '{}'", flags)),
+        };
+        if *slot {
+            return Err(format!("invalid flags '{}'", flags));
+        }
+        *slot = true;
+    }
+    Ok(parsed)
+}
+
+/// Rewrites a JavaScript pattern into `regex` crate syntax. The character class
+/// escapes are narrowed to JavaScript's ASCII definitions, `.` stops at every line
+/// terminator, and syntax the crate reads differently inside classes is escaped.
+fn translate_pattern(pattern: &str, dot_all: bool) -> String {
+    let mut out = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars().peekable();
+    let mut in_class = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                let Some(escaped) = chars.next() else {
+                    out.push('\\');
+                    break;
+                };
+                match escaped {
+                    'd' => out.push_str("[0-9]"),
+                    'D' => out.push_str("[^0-9]"),
+                    'w' => out.push_str("[0-9A-Za-z_]"),
+                    'W' => out.push_str("[^0-9A-Za-z_]"),
+                    's' => out.push_str(&format!("[{}]", JS_WHITESPACE)),
+                    'S' => out.push_str(&format!("[^{}]", JS_WHITESPACE)),
+                    // Inside a class `\b` is a backspace
+                    'b' if in_class => out.push_str(r"\x08"),
+                    'b' => out.push_str(r"(?-u:\b)"),
+                    'B' => out.push_str(r"(?-u:\B)"),
+                    '0' if !chars.peek().is_some_and(char::is_ascii_digit) => out.push_str(r"\x00"),
+                    'u' => {
+                        // `\uXXXX` and `\u{X...}`; anything else is a literal `u`
+                        let rest: String = chars.clone().collect();
+                        let braced = rest
+                            .strip_prefix('{')
+                            .and_then(|rest| rest.split_once('}'))
+                            .map(|(digits, _)| digits)
+                            .filter(|digits| {
+                                !digits.is_empty() && digits.chars().all(|c| c.is_ascii_hexdigit())
+                            });
+                        let fixed: String = rest.chars().take(4).collect();
+                        if let Some(digits) = braced {
+                            out.push_str(&format!(r"\x{{{}}}", digits));
+                            chars.nth(digits.len() + 1);
+                        } else if fixed.len() == 4 && fixed.chars().all(|c| c.is_ascii_hexdigit()) {
+                            out.push_str(&format!(r"\x{{{}}}", fixed));
+                            chars.nth(3);
+                        } else {
+                            out.push('u');
+                        }
+                    }
+                    'c' if chars.peek().is_some_and(char::is_ascii_alphabetic) => {
+                        let letter = chars.next().unwrap();
+                        out.push_str(&format!(r"\x{:02X}", letter as u32 % 32));
+                    }
+                    '/' => out.push('/'),
+                    other => {
+                        out.push('\\');
+                        out.push(other);
+                    }
+                }
+            }
+            '[' if !in_class => {
+                let mut lookahead = chars.clone();
+                match (lookahead.next(), lookahead.next()) {
+                    // `[^]` matches any character and `[]` matches nothing
+                    (Some('^'), Some(']')) => {
+                        out.push_str("(?s:.)");
+                        chars.nth(1);
+                    }
+                    (Some(']'), _) => {
+                        out.push_str(r"[^\x00-\x{10FFFF}]");
+                        chars.next();
+                    }
+                    _ => {
+                        out.push('[');
+                        in_class = true;
+                        if chars.next_if_eq(&'^').is_some() {
+                            out.push('^');
+                        }
+                    }
+                }
+            }
+            ']' if in_class => {
+                out.push(']');
+                in_class = false;
+            }
+            // Nested classes and set operations are not JavaScript syntax
+            '[' | '&' | '~' if in_class => {
+                out.push('\\');
+                out.push(c);
+            }
+            '.' if !in_class && !dot_all => out.push_str(r"[^\n\r\x{2028}\x{2029}]"),
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+/// Compiled `new RegExp(...)` patterns, shared by every evaluation of one compiled
+/// expression so that a rule like `new RegExp(field.pattern).test(x)` only compiles
+/// each distinct pattern once.
+#[derive(Debug, Default)]
+pub(crate) struct RegExpCache {
+    entries: Mutex<HashMap<(String, String), RegExp>>,
+}
+
+impl RegExpCache {
+    pub(crate) fn get_or_compile(&self, source: &str, flags: &str) -> Result<RegExp, String> {
+        let key = (source.to_string(), flags.to_string());
+        let mut entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(regexp) = entries.get(&key) {
+            return Ok(regexp.clone());
+        }
+
+        let regexp = RegExp::new(source, flags)?;
+        // Patterns built from input could otherwise grow the cache without bound
+        if entries.len() >= MAX_CACHED_REGEXPS {
+            entries.clear();
+        }
+        entries.insert(key, regexp.clone());
+        Ok(regexp)
+    }
+}
+
+impl PartialEq for RegExpCache {
+    /// The cache never affects results, so it is ignored when comparing expressions.
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
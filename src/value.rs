@@ -0,0 +1,560 @@
+use crate::{EvaluationError, Function};
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::fmt;
+use std::str::FromStr;
+
+/// The largest integer `n` for which `n` and `n + 1` are both exactly representable
+/// as an `f64`.
+pub(crate) const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+
+/// The map behind [`Value::Object`]. Keys are sorted by default, or kept in insertion
+/// order with the `preserve_order` feature, the same as `serde_json::Map`.
+#[cfg(not(feature = "preserve_order"))]
+pub type Map = std::collections::BTreeMap<String, Value>;
+#[cfg(feature = "preserve_order")]
+pub type Map = indexmap::IndexMap<String, Value>;
+
+/// A value produced while evaluating an expression.
+///
+/// Unlike `serde_json::Value` this can hold every primitive an expression may produce,
+/// including `undefined`, `NaN`, the infinities, negative zero and BigInts. Results are
+/// converted to JSON at the API boundary as the evaluator's [`NonJsonPolicy`] says;
+/// [`Evaluator::evaluate_value`](crate::Evaluator::evaluate_value) returns them as they
+/// are.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Value {
+    #[default]
+    Undefined,
+    Null,
+    Bool(bool),
+    Number(Number),
+    String(String),
+    Array(Vec<Value>),
+    Object(Map),
+    /// An arbitrary-precision integer such as `123n`, only produced when
+    /// [`Evaluator::with_bigint`](crate::Evaluator::with_bigint) is enabled.
+    BigInt(BigInt),
+    /// An arrow function such as `x => x * 2`. Functions have no JSON form and convert
+    /// like `undefined`.
+    Function(Function),
+}
+
+/// A number. Integers keep their exact value: arithmetic on two integers stays exact
+/// as long as the result fits in 64 bits, and whole results within the safe range
+/// (±2^53 - 1) are stored as integers. Anything else is an `f64`, which may be `NaN`,
+/// infinite or `-0`, or a [`Decimal`] when
+/// [`Evaluator::with_decimal`](crate::Evaluator::with_decimal) is enabled.
+///
+/// Numbers compare by numeric value, so `NaN` is not equal to itself and `0` equals
+/// `-0`.
+#[derive(Clone, Copy)]
+pub struct Number {
+    n: N,
+}
+
+#[derive(Clone, Copy)]
+enum N {
+    PosInt(u64),
+    /// Always less than zero.
+    NegInt(i64),
+    Float(f64),
+    /// Never a whole number that fits in 64 bits, which is stored as an integer.
+    Decimal(Decimal),
+}
+
+/// What becomes of values that JSON cannot represent when a result is converted to a
+/// `serde_json::Value`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NonJsonPolicy {
+    /// Do what `JSON.stringify` does: `NaN`, the infinities, `undefined` and functions
+    /// become null, except that object properties holding `undefined` or a function are
+    /// left out. BigInts that do not fit in 64 bits also become null.
+    #[default]
+    Null,
+    /// Fail with [`EvaluationError::NonJsonValue`].
+    Error,
+    /// Write `NaN` and the infinities as the strings `"NaN"`, `"Infinity"` and
+    /// `"-Infinity"`, and BigInts that do not fit in 64 bits as their digits.
+    /// `undefined` and functions are handled as with [`NonJsonPolicy::Null`].
+    String,
+}
+
+/// What happens when an integer beyond ±2^53 - 1 has to be converted to an `f64`, for
+/// example when it is mixed with a fraction, passed to `Math` or when integer
+/// arithmetic overflows 64 bits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnsafeIntegerPolicy {
+    /// Round to the nearest `f64`, as JavaScript does.
+    #[default]
+    Round,
+    /// Fail with [`EvaluationError::UnsafeInteger`] instead of losing precision.
+    Error,
+}
+
+/// How decimal arithmetic rounds its results, set by
+/// [`Evaluator::with_decimal`](crate::Evaluator::with_decimal).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DecimalMode {
+    pub(crate) scale: u32,
+    pub(crate) rounding: RoundingStrategy,
+}
+
+impl Value {
+    pub fn is_undefined(&self) -> bool {
+        matches!(self, Value::Undefined)
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// Whether the value is null or undefined, the values `??` and `?.` skip over.
+    pub fn is_nullish(&self) -> bool {
+        matches!(self, Value::Undefined | Value::Null)
+    }
+
+    pub fn is_array(&self) -> bool {
+        matches!(self, Value::Array(_))
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(n.as_f64()),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<Value>> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&Map> {
+        match self {
+            Value::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    /// Converts the value to JSON, using `policy` for `undefined`, functions, `NaN` and
+    /// the infinities. Everything else, including exact integers and `-0`, converts
+    /// losslessly.
+    pub fn to_json(&self, policy: NonJsonPolicy) -> Result<serde_json::Value, EvaluationError> {
+        let json = match self {
+            Value::Undefined => match policy {
+                NonJsonPolicy::Error => return Err(non_json_error("undefined")),
+                NonJsonPolicy::Null | NonJsonPolicy::String => serde_json::Value::Null,
+            },
+            Value::Null => serde_json::Value::Null,
+            Value::Bool(b) => serde_json::Value::Bool(*b),
+            Value::Number(n) => match n.n {
+                N::PosInt(u) => serde_json::Value::from(u),
+                N::NegInt(i) => serde_json::Value::from(i),
+                // Without `arbitrary_precision` the digits are parsed into an `f64`
+                N::Decimal(d) => serde_json::Number::from_str(&d.to_string())
+                    .map(serde_json::Value::Number)
+                    .unwrap_or_else(|_| serde_json::Value::from(n.as_f64())),
+                N::Float(f) => match serde_json::Number::from_f64(f) {
+                    Some(number) => serde_json::Value::Number(number),
+                    None => match policy {
+                        NonJsonPolicy::Null => serde_json::Value::Null,
+                        NonJsonPolicy::Error => return Err(non_json_error(&n.to_string())),
+                        NonJsonPolicy::String => serde_json::Value::String(n.to_string()),
+                    },
+                },
+            },
+            Value::String(s) => serde_json::Value::String(s.clone()),
+            Value::Array(items) => serde_json::Value::Array(
+                items
+                    .iter()
+                    .map(|item| item.to_json(policy))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Value::Object(map) => {
+                let mut object = serde_json::Map::new();
+                for (key, value) in map {
+                    if matches!(value, Value::Undefined | Value::Function(_))
+                        && policy != NonJsonPolicy::Error
+                    {
+                        continue;
+                    }
+                    object.insert(key.clone(), value.to_json(policy)?);
+                }
+                serde_json::Value::Object(object)
+            }
+            Value::BigInt(b) => match (b.to_u64(), b.to_i64()) {
+                (Some(u), _) => serde_json::Value::from(u),
+                (None, Some(i)) => serde_json::Value::from(i),
+                (None, None) => match policy {
+                    NonJsonPolicy::Null => serde_json::Value::Null,
+                    NonJsonPolicy::Error => return Err(non_json_error(&format!("{}n", b))),
+                    NonJsonPolicy::String => serde_json::Value::String(b.to_string()),
+                },
+            },
+            Value::Function(function) => match policy {
+                NonJsonPolicy::Error => return Err(non_json_error(function.source())),
+                NonJsonPolicy::Null | NonJsonPolicy::String => serde_json::Value::Null,
+            },
+        };
+        Ok(json)
+    }
+}
+
+fn non_json_error(value: &str) -> EvaluationError {
+    EvaluationError::NonJsonValue(value.to_string(), None)
+}
+
+impl Number {
+    pub fn from_f64(f: f64) -> Self {
+        Number { n: N::Float(f) }
+    }
+
+    /// The number as an `f64`, rounding integers beyond 2^53 and decimals with more
+    /// digits than an `f64` holds.
+    pub fn as_f64(&self) -> f64 {
+        match self.n {
+            N::PosInt(u) => u as f64,
+            N::NegInt(i) => i as f64,
+            N::Float(f) => f,
+            // Parsing the digits rounds correctly, unlike `Decimal::to_f64`
+            N::Decimal(d) => d.to_string().parse().unwrap_or(f64::NAN),
+        }
+    }
+
+    /// The number as an `i64` if it is an integer read from JSON that fits.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.n {
+            N::PosInt(u) => i64::try_from(u).ok(),
+            N::NegInt(i) => Some(i),
+            N::Float(_) | N::Decimal(_) => None,
+        }
+    }
+
+    /// The number as a `u64` if it is a non-negative integer read from JSON.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self.n {
+            N::PosInt(u) => Some(u),
+            N::NegInt(_) | N::Float(_) | N::Decimal(_) => None,
+        }
+    }
+
+    /// The exact value of a decimal or an integer. Floats have no exact decimal value
+    /// and give `None`.
+    pub fn as_decimal(&self) -> Option<Decimal> {
+        match self.n {
+            N::PosInt(u) => Some(Decimal::from(u)),
+            N::NegInt(i) => Some(Decimal::from(i)),
+            N::Float(_) => None,
+            N::Decimal(d) => Some(d),
+        }
+    }
+
+    pub fn is_f64(&self) -> bool {
+        matches!(self.n, N::Float(_))
+    }
+
+    /// Whether the number is a fraction computed by decimal arithmetic.
+    pub fn is_decimal(&self) -> bool {
+        matches!(self.n, N::Decimal(_))
+    }
+
+    pub fn is_nan(&self) -> bool {
+        matches!(self.n, N::Float(f) if f.is_nan())
+    }
+
+    /// A number computed as an `f64`. Whole values within the safe range are stored as
+    /// integers, so `[1, 2].length` is `2` rather than `2.0`; `-0` stays a float.
+    pub(crate) fn from_computed(f: f64) -> Self {
+        if f.fract() == 0.0 && f.abs() <= MAX_SAFE_INTEGER && !(f == 0.0 && f.is_sign_negative()) {
+            Number::from(f as i64)
+        } else {
+            Number::from_f64(f)
+        }
+    }
+
+    /// A number computed as a decimal. Whole values that fit in 64 bits are stored as
+    /// integers, as with [`Number::from_computed`].
+    pub(crate) fn from_decimal(d: Decimal) -> Self {
+        match d.is_integer().then(|| d.to_i128()).flatten() {
+            Some(i) => Number::from_integer(i).unwrap_or(Number { n: N::Decimal(d) }),
+            None => Number {
+                n: N::Decimal(d.normalize()),
+            },
+        }
+    }
+
+    /// An exact integer, or `None` if it does not fit in 64 bits.
+    pub(crate) fn from_integer(i: i128) -> Option<Self> {
+        if let Ok(u) = u64::try_from(i) {
+            Some(Number::from(u))
+        } else {
+            i64::try_from(i).ok().map(Number::from)
+        }
+    }
+
+    /// The exact value of an integer, or `None` for a float.
+    pub(crate) fn as_integer(&self) -> Option<i128> {
+        match self.n {
+            N::PosInt(u) => Some(i128::from(u)),
+            N::NegInt(i) => Some(i128::from(i)),
+            N::Float(_) | N::Decimal(_) => None,
+        }
+    }
+
+    /// Whether this is an integer beyond ±2^53 - 1, which may not survive conversion to
+    /// an `f64`.
+    pub(crate) fn is_unsafe_integer(&self) -> bool {
+        self.as_integer()
+            .is_some_and(|i| i.unsigned_abs() > MAX_SAFE_INTEGER as u128)
+    }
+}
+
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.n, other.n) {
+            (N::PosInt(a), N::PosInt(b)) => a == b,
+            (N::NegInt(a), N::NegInt(b)) => a == b,
+            (N::PosInt(_), N::NegInt(_)) | (N::NegInt(_), N::PosInt(_)) => false,
+            (N::Decimal(_), _) | (_, N::Decimal(_)) => {
+                match (self.as_decimal(), other.as_decimal()) {
+                    (Some(a), Some(b)) => a == b,
+                    _ => self.as_f64() == other.as_f64(),
+                }
+            }
+            _ => self.as_f64() == other.as_f64(),
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    /// Formats the number as JavaScript's `String(n)` would.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.n {
+            N::PosInt(u) => write!(f, "{}", u),
+            N::NegInt(i) => write!(f, "{}", i),
+            N::Float(n) => f.write_str(&number_to_js_string(n)),
+            // Decimals are written out in full, e.g. `0.0000001` rather than `1e-7`
+            N::Decimal(d) => write!(f, "{}", d),
+        }
+    }
+}
+
+impl fmt::Debug for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Number({})", self)
+    }
+}
+
+impl From<f64> for Number {
+    fn from(f: f64) -> Self {
+        Number::from_f64(f)
+    }
+}
+
+impl From<u64> for Number {
+    fn from(u: u64) -> Self {
+        Number { n: N::PosInt(u) }
+    }
+}
+
+impl From<i64> for Number {
+    fn from(i: i64) -> Self {
+        if i < 0 {
+            Number { n: N::NegInt(i) }
+        } else {
+            Number::from(i as u64)
+        }
+    }
+}
+
+impl From<Decimal> for Number {
+    fn from(d: Decimal) -> Self {
+        Number::from_decimal(d)
+    }
+}
+
+impl From<&serde_json::Number> for Number {
+    fn from(n: &serde_json::Number) -> Self {
+        if let Some(u) = n.as_u64() {
+            Number::from(u)
+        } else if let Some(i) = n.as_i64() {
+            Number::from(i)
+        } else {
+            Number::from_f64(n.as_f64().unwrap_or(f64::NAN))
+        }
+    }
+}
+
+impl From<serde_json::Value> for Value {
+    fn from(json: serde_json::Value) -> Self {
+        match json {
+            serde_json::Value::Null => Value::Null,
+            serde_json::Value::Bool(b) => Value::Bool(b),
+            serde_json::Value::Number(n) => Value::Number(Number::from(&n)),
+            serde_json::Value::String(s) => Value::String(s),
+            serde_json::Value::Array(items) => {
+                Value::Array(items.into_iter().map(Value::from).collect())
+            }
+            serde_json::Value::Object(map) => Value::Object(
+                map.into_iter()
+                    .map(|(key, value)| (key, Value::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<&serde_json::Value> for Value {
+    fn from(json: &serde_json::Value) -> Self {
+        match json {
+            serde_json::Value::Null => Value::Null,
+            serde_json::Value::Bool(b) => Value::Bool(*b),
+            serde_json::Value::Number(n) => Value::Number(Number::from(n)),
+            serde_json::Value::String(s) => Value::String(s.clone()),
+            serde_json::Value::Array(items) => {
+                Value::Array(items.iter().map(Value::from).collect())
+            }
+            serde_json::Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(key, value)| (key.clone(), Value::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(f: f64) -> Self {
+        Value::Number(Number::from_f64(f))
+    }
+}
+
+impl From<i64> for Value {
+    fn from(i: i64) -> Self {
+        Value::Number(Number::from(i))
+    }
+}
+
+impl From<u64> for Value {
+    fn from(u: u64) -> Self {
+        Value::Number(Number::from(u))
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl From<BigInt> for Value {
+    fn from(b: BigInt) -> Self {
+        Value::BigInt(b)
+    }
+}
+
+impl From<Decimal> for Value {
+    fn from(d: Decimal) -> Self {
+        Value::Number(Number::from_decimal(d))
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(items: Vec<Value>) -> Self {
+        Value::Array(items)
+    }
+}
+
+/// Reads a decimal number such as `0.1` or `1.5e-7` exactly, or `None` if it does not
+/// fit in a [`Decimal`].
+pub(crate) fn parse_decimal(s: &str) -> Option<Decimal> {
+    if s.contains(['e', 'E']) {
+        Decimal::from_scientific(s).ok()
+    } else {
+        Decimal::from_str(s).ok()
+    }
+}
+
+/// Formats a number the way JavaScript's `Number.prototype.toString` does: integral
+/// values have no fractional part and very large or small magnitudes use exponent
+/// notation.
+pub(crate) fn number_to_js_string(n: f64) -> String {
+    if n.is_nan() {
+        return "NaN".to_string();
+    }
+    if n.is_infinite() {
+        return if n > 0.0 { "Infinity" } else { "-Infinity" }.to_string();
+    }
+    if n == 0.0 {
+        return "0".to_string();
+    }
+
+    // `{:e}` yields the shortest round-tripping digits, e.g. "1.25e-7"
+    let formatted = format!("{:e}", n.abs());
+    let (mantissa, exponent) = formatted.split_once('e').unwrap();
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let exponent: i32 = exponent.parse().unwrap();
+    let k = digits.len() as i32;
+    let point = exponent + 1;
+
+    let body = if k <= point && point <= 21 {
+        format!("{}{}", digits, "0".repeat((point - k) as usize))
+    } else if 0 < point && point <= 21 {
+        format!(
+            "{}.{}",
+            &digits[..point as usize],
+            &digits[point as usize..]
+        )
+    } else if -6 < point && point <= 0 {
+        format!("0.{}{}", "0".repeat((-point) as usize), digits)
+    } else {
+        let sign = if exponent < 0 { '-' } else { '+' };
+        if k == 1 {
+            format!("{}e{}{}", digits, sign, exponent.abs())
+        } else {
+            format!(
+                "{}.{}e{}{}",
+                &digits[..1],
+                &digits[1..],
+                sign,
+                exponent.abs()
+            )
+        }
+    };
+
+    if n < 0.0 {
+        format!("-{}", body)
+    } else {
+        body
+    }
+}
@@ -0,0 +1,341 @@
+use exprimo::{CustomFuncError, EvaluationError, Evaluator};
+use serde_json::{json, Value};
+
+mod common;
+use common::evaluator_with;
+
+fn orders_evaluator() -> Evaluator {
+    evaluator_with(json!({
+        "orders": [
+            { "id": "a", "total": 40, "items": [1, 2] },
+            { "id": "b", "total": 150, "items": [3] },
+            { "id": "c", "total": 90, "items": [] }
+        ],
+        "threshold": 100
+    }))
+}
+
+// --- Callback methods ---
+
+#[test]
+fn test_array_map_and_filter() {
+    let evaluator = orders_evaluator();
+
+    assert_eq!(
+        evaluator.evaluate("orders.map(o => o.id)").unwrap(),
+        json!(["a", "b", "c"])
+    );
+    assert_eq!(
+        evaluator
+            .evaluate("orders.filter(o => o.total < threshold).map(o => o.id)")
+            .unwrap(),
+        json!(["a", "c"])
+    );
+    assert_eq!(
+        evaluator.evaluate("orders.map((o, i) => i)").unwrap(),
+        json!([0, 1, 2])
+    );
+    assert_eq!(
+        evaluator
+            .evaluate("[1, 2, 3].map((n, i, all) => n * all.length)")
+            .unwrap(),
+        json!([3, 6, 9])
+    );
+    // Filter uses truthiness
+    assert_eq!(
+        evaluator
+            .evaluate("[0, 1, '', 'x', null].filter(v => v)")
+            .unwrap(),
+        json!([1, "x"])
+    );
+}
+
+#[test]
+fn test_array_find_some_every() {
+    let evaluator = orders_evaluator();
+
+    assert_eq!(
+        evaluator
+            .evaluate("orders.find(o => o.total > 50).id")
+            .unwrap(),
+        json!("b")
+    );
+    assert_eq!(
+        evaluator
+            .evaluate("orders.find(o => o.total > 500)")
+            .unwrap(),
+        Value::Null
+    );
+    assert_eq!(
+        evaluator
+            .evaluate("orders.findIndex(o => o.id === 'c')")
+            .unwrap(),
+        json!(2)
+    );
+    assert_eq!(
+        evaluator
+            .evaluate("orders.findIndex(o => o.id === 'z')")
+            .unwrap(),
+        json!(-1)
+    );
+    assert_eq!(
+        evaluator
+            .evaluate("orders.some(o => o.total > 100)")
+            .unwrap(),
+        Value::Bool(true)
+    );
+    assert_eq!(
+        evaluator
+            .evaluate("orders.every(o => o.total > 100)")
+            .unwrap(),
+        Value::Bool(false)
+    );
+    assert_eq!(
+        evaluator.evaluate("[].every(o => false)").unwrap(),
+        Value::Bool(true)
+    );
+    assert_eq!(
+        evaluator.evaluate("[].some(o => true)").unwrap(),
+        Value::Bool(false)
+    );
+}
+
+#[test]
+fn test_array_callbacks_short_circuit() {
+    let evaluator = evaluator_with(json!({ "list": [1, 2, 3] }));
+
+    // `some` stops at the first element, so `missing` is never evaluated
+    assert_eq!(
+        evaluator
+            .evaluate("list.some(n => n === 1 || missing)")
+            .unwrap(),
+        Value::Bool(true)
+    );
+    assert!(evaluator
+        .evaluate("list.map(n => n === 2 || missing)")
+        .is_err());
+}
+
+#[test]
+fn test_array_reduce() {
+    let evaluator = orders_evaluator();
+
+    assert_eq!(
+        evaluator
+            .evaluate("orders.reduce((sum, o) => sum + o.total, 0)")
+            .unwrap(),
+        json!(280)
+    );
+    assert_eq!(
+        evaluator
+            .evaluate("[1, 2, 3].reduce((a, b) => a * b)")
+            .unwrap(),
+        json!(6)
+    );
+    assert_eq!(
+        evaluator
+            .evaluate("['a', 'b'].reduce((acc, s, i) => i === 0 ? acc + s : acc + '-' + s, '')")
+            .unwrap(),
+        json!("a-b")
+    );
+
+    let result = evaluator.evaluate("[].reduce((a, b) => a + b)");
+    match result {
+        Err(EvaluationError::TypeError(msg, _)) => {
+            assert!(msg.contains("Reduce of empty array"), "{}", msg)
+        }
+        _ => panic!("Expected TypeError, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_array_flat_and_flat_map() {
+    let evaluator = orders_evaluator();
+
+    assert_eq!(
+        evaluator.evaluate("orders.flatMap(o => o.items)").unwrap(),
+        json!([1, 2, 3])
+    );
+    assert_eq!(
+        evaluator.evaluate("[1, [2, [3, [4]]]].flat()").unwrap(),
+        json!([1, 2, [3, [4]]])
+    );
+    assert_eq!(
+        evaluator.evaluate("[1, [2, [3, [4]]]].flat(2)").unwrap(),
+        json!([1, 2, 3, [4]])
+    );
+    assert_eq!(
+        evaluator
+            .evaluate("[1, [2, [3, [4]]]].flat(Infinity)")
+            .unwrap(),
+        json!([1, 2, 3, 4])
+    );
+    assert_eq!(
+        evaluator.evaluate("[1, [2]].flat(0)").unwrap(),
+        json!([1, [2]])
+    );
+}
+
+// --- Value methods ---
+
+#[test]
+fn test_array_index_of() {
+    let evaluator = evaluator_with(json!({ "list": ["a", "b", "a", 1] }));
+
+    for (expr, expected) in [
+        ("list.indexOf('a')", 0.0),
+        ("list.indexOf('a', 1)", 2.0),
+        ("list.indexOf('a', -2)", 2.0),
+        ("list.indexOf('z')", -1.0),
+        // Strict equality: no coercion
+        ("list.indexOf('1')", -1.0),
+        ("list.indexOf(1)", 3.0),
+        ("list.lastIndexOf('a')", 2.0),
+        ("list.lastIndexOf('a', 1)", 0.0),
+        ("list.lastIndexOf('a', -3)", 0.0),
+        ("list.lastIndexOf('a', -10)", -1.0),
+        ("[].lastIndexOf('a')", -1.0),
+    ] {
+        assert_eq!(
+            evaluator.evaluate(expr).unwrap().as_f64(),
+            Some(expected),
+            "{}",
+            expr
+        );
+    }
+}
+
+#[test]
+fn test_array_slice_and_at() {
+    let evaluator = evaluator_with(json!({ "list": [1, 2, 3, 4, 5] }));
+
+    for (expr, expected) in [
+        ("list.slice()", json!([1, 2, 3, 4, 5])),
+        ("list.slice(2)", json!([3, 4, 5])),
+        ("list.slice(1, 3)", json!([2, 3])),
+        ("list.slice(-2)", json!([4, 5])),
+        ("list.slice(-10, -3)", json!([1, 2])),
+        ("list.slice(3, 1)", json!([])),
+        ("list.slice(1.7, 10)", json!([2, 3, 4, 5])),
+        ("list.at(0)", json!(1)),
+        ("list.at(-1)", json!(5)),
+        ("list.at(5)", Value::Null),
+        ("list.at(-6)", Value::Null),
+        ("list.at()", json!(1)),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+}
+
+#[test]
+fn test_array_concat_and_join() {
+    let evaluator = evaluator_with(json!({ "a": [1, 2], "b": [[3]] }));
+
+    assert_eq!(
+        evaluator.evaluate("a.concat(b, 4, [5])").unwrap(),
+        json!([1, 2, [3], 4, 5])
+    );
+    assert_eq!(evaluator.evaluate("a.join()").unwrap(), json!("1,2"));
+    assert_eq!(evaluator.evaluate("a.join(' - ')").unwrap(), json!("1 - 2"));
+    assert_eq!(
+        evaluator
+            .evaluate("[1.5, null, 'x', true, [2, 3]].join('|')")
+            .unwrap(),
+        json!("1.5||x|true|2,3")
+    );
+    assert_eq!(evaluator.evaluate("[].join()").unwrap(), json!(""));
+}
+
+#[test]
+fn test_array_sort_and_reverse() {
+    let evaluator = evaluator_with(json!({
+        "nums": [10, 9, 1, 100],
+        "words": ["pear", "Apple", "banana"],
+        "people": [
+            { "name": "a", "age": 30 },
+            { "name": "b", "age": 25 },
+            { "name": "c", "age": 30 }
+        ]
+    }));
+
+    // The default order compares strings, as in JavaScript
+    assert_eq!(
+        evaluator.evaluate("nums.sort()").unwrap(),
+        json!([1, 10, 100, 9])
+    );
+    assert_eq!(
+        evaluator
+            .evaluate("nums.toSorted((a, b) => a - b)")
+            .unwrap(),
+        json!([1, 9, 10, 100])
+    );
+    assert_eq!(
+        evaluator.evaluate("words.toSorted()").unwrap(),
+        json!(["Apple", "banana", "pear"])
+    );
+    // Stable, and the original array is not modified
+    assert_eq!(
+        evaluator
+            .evaluate("people.sort((a, b) => b.age - a.age).map(p => p.name)")
+            .unwrap(),
+        json!(["a", "c", "b"])
+    );
+    assert_eq!(
+        evaluator.evaluate("people.map(p => p.name)").unwrap(),
+        json!(["a", "b", "c"])
+    );
+    // Nulls sort last
+    assert_eq!(
+        evaluator.evaluate("[3, null, 1].sort()").unwrap(),
+        json!([1, 3, null])
+    );
+    // An inconsistent comparator does not panic
+    assert!(evaluator.evaluate("nums.sort(() => 1)").is_ok());
+
+    assert_eq!(
+        evaluator.evaluate("nums.reverse()").unwrap(),
+        json!([100, 1, 9, 10])
+    );
+    assert_eq!(
+        evaluator.evaluate("nums.toReversed()").unwrap(),
+        json!([100, 1, 9, 10])
+    );
+}
+
+#[test]
+fn test_array_keys_and_entries() {
+    let evaluator = evaluator_with(json!({ "list": ["x", "y"] }));
+
+    assert_eq!(evaluator.evaluate("list.keys()").unwrap(), json!([0, 1]));
+    assert_eq!(
+        evaluator.evaluate("list.entries()").unwrap(),
+        json!([[0, "x"], [1, "y"]])
+    );
+}
+
+// --- Errors ---
+
+#[test]
+fn test_array_method_argument_errors() {
+    let evaluator = orders_evaluator();
+
+    let result = evaluator.evaluate("orders.map()");
+    match result {
+        Err(EvaluationError::CustomFunction(CustomFuncError::ArityError { expected, got }, _)) => {
+            assert_eq!((expected, got), (1, 0));
+        }
+        _ => panic!("Expected ArityError, got {:?}", result),
+    }
+
+    let result = evaluator.evaluate("orders.map(1)");
+    match result {
+        Err(EvaluationError::TypeError(msg, _)) => assert!(msg.contains("is not a function")),
+        _ => panic!("Expected TypeError, got {:?}", result),
+    }
+
+    // Functions are values, so `indexOf` looks for one instead of calling it
+    assert_eq!(
+        evaluator.evaluate("orders.indexOf(o => o)").unwrap(),
+        json!(-1)
+    );
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::needless_borrow, unused_variables)]
+
 use exprimo::Evaluator;
 use std::collections::HashMap;
 
@@ -55,11 +57,12 @@ fn test_basic_evaluate_with_nulls() {
     let res4 = evaluator.evaluate(&expr4).unwrap();
     let res5 = evaluator.evaluate(&expr5).unwrap();
 
-    assert_eq!(res1, false);
+    // `&&` and `||` return the deciding operand, so a falsy `a` comes back as null.
+    assert_eq!(res1, serde_json::Value::Null);
     assert_eq!(res2, true);
-    assert_eq!(res3, false);
+    assert_eq!(res3, serde_json::Value::Null);
     assert_eq!(res4, false);
-    assert_eq!(res5, false);
+    assert_eq!(res5, serde_json::Value::Null);
 }
 
 // #[test]
@@ -163,19 +166,19 @@ fn test_parenthesized_expressions() {
     let mut context = HashMap::new();
     context.insert(
         "a".to_string(),
-        serde_json::Value::Number(serde_json::Number::from_f64(1.0).unwrap()),
+        serde_json::Value::Number(serde_json::Number::from(1)),
     );
     context.insert(
         "b".to_string(),
-        serde_json::Value::Number(serde_json::Number::from_f64(2.0).unwrap()),
+        serde_json::Value::Number(serde_json::Number::from(2)),
     );
     context.insert(
         "c".to_string(),
-        serde_json::Value::Number(serde_json::Number::from_f64(3.0).unwrap()),
+        serde_json::Value::Number(serde_json::Number::from(3)),
     );
     context.insert(
         "d".to_string(),
-        serde_json::Value::Number(serde_json::Number::from_f64(4.0).unwrap()),
+        serde_json::Value::Number(serde_json::Number::from(4)),
     );
 
     let evaluator = Evaluator::new(
@@ -186,10 +189,7 @@ fn test_parenthesized_expressions() {
     // Simple case: (1 + 2) * 3 = 9
     let expr1 = "(a + b) * c";
     let res1 = evaluator.evaluate(expr1).unwrap();
-    assert_eq!(
-        res1,
-        serde_json::Value::Number(serde_json::Number::from_f64(9.0).unwrap())
-    );
+    assert_eq!(res1, serde_json::Value::Number(serde_json::Number::from(9)));
 
     // Nested parentheses: ((1 + 2) * 3) / 4 = 2.25
     let expr2 = "((a + b) * c) / d";
@@ -203,17 +203,14 @@ fn test_parenthesized_expressions() {
     // Using direct numbers for clarity here, assuming context a,b,c,d are not used or are shadowed by literals
     let expr3 = "((d-b) * ((a+b)*c)) / (10/5)";
     let res3 = evaluator.evaluate(expr3).unwrap();
-    assert_eq!(
-        res3,
-        serde_json::Value::Number(serde_json::Number::from_f64(9.0).unwrap())
-    );
+    assert_eq!(res3, serde_json::Value::Number(serde_json::Number::from(9)));
 
     // Expression with unary operator
     let expr4 = "-(a + b)";
     let res4 = evaluator.evaluate(expr4).unwrap();
     assert_eq!(
         res4,
-        serde_json::Value::Number(serde_json::Number::from_f64(-3.0).unwrap())
+        serde_json::Value::Number(serde_json::Number::from(-3))
     );
 
     // Expression with unary operator inside parentheses
@@ -221,7 +218,7 @@ fn test_parenthesized_expressions() {
     let res5 = evaluator.evaluate(expr5).unwrap();
     assert_eq!(
         res5,
-        serde_json::Value::Number(serde_json::Number::from_f64(-9.0).unwrap())
+        serde_json::Value::Number(serde_json::Number::from(-9))
     );
 
     // Expression with boolean logic
@@ -321,11 +318,12 @@ fn test_object_has_own_property_success() {
 
     let evaluator3_num = Evaluator::new(context3, HashMap::new());
 
+    // The number 123 is converted to the key "123", as in JavaScript
     assert_eq!(
         evaluator3_num
             .evaluate("objNumStrKey.hasOwnProperty(123)")
             .unwrap(),
-        Value::Bool(false)
+        Value::Bool(true)
     );
     assert_eq!(
         evaluator3_num
@@ -343,7 +341,7 @@ fn test_object_has_own_property_arity_error() {
 
     let res_no_args = evaluator.evaluate("myObj.hasOwnProperty()");
     match res_no_args {
-        Err(EvaluationError::CustomFunction(CustomFuncError::ArityError { expected, got })) => {
+        Err(EvaluationError::CustomFunction(CustomFuncError::ArityError { expected, got }, _)) => {
             assert_eq!(expected, 1);
             assert_eq!(got, 0);
         }
@@ -355,7 +353,7 @@ fn test_object_has_own_property_arity_error() {
 
     let res_many_args = evaluator.evaluate("myObj.hasOwnProperty('prop', 'extra')");
     match res_many_args {
-        Err(EvaluationError::CustomFunction(CustomFuncError::ArityError { expected, got })) => {
+        Err(EvaluationError::CustomFunction(CustomFuncError::ArityError { expected, got }, _)) => {
             assert_eq!(expected, 1);
             assert_eq!(got, 2);
         }
@@ -378,10 +376,10 @@ fn test_object_has_own_property_on_non_object() {
     let expr_arr = "myArr.hasOwnProperty('length')";
     let result_arr = evaluator.evaluate(expr_arr);
     match result_arr {
-        Err(EvaluationError::TypeError(msg)) => {
+        Err(EvaluationError::TypeError(msg, _)) => {
             assert_eq!(
                 msg,
-                "'null' (resulting from expression 'myArr.hasOwnProperty') is not a function."
+                "'undefined' (resulting from expression 'myArr.hasOwnProperty') is not a function."
             );
         }
         _ => panic!(
@@ -394,7 +392,7 @@ fn test_object_has_own_property_on_non_object() {
     let expr_str = "myStr.hasOwnProperty('length')";
     let result_str = evaluator.evaluate(expr_str);
     match result_str {
-        Err(EvaluationError::TypeError(msg)) => {
+        Err(EvaluationError::TypeError(msg, _)) => {
             assert_eq!(msg, "Cannot read properties of null or primitive value: text (trying to access property: hasOwnProperty)");
         }
         _ => panic!(
@@ -479,6 +477,23 @@ fn test_array_includes_success() {
         evaluator.evaluate("myArr.includes({})").unwrap(),
         Value::Bool(false)
     );
+
+    // The search starts at `fromIndex`, counting from the end when negative
+    for (expr, expected) in [
+        ("[1, 2, 3].includes(1, 1)", false),
+        ("[1, 2, 3].includes(3, 2)", true),
+        ("[1, 2, 3].includes(1, -1)", false),
+        ("[1, 2, 3].includes(1, -10)", true),
+        ("[1, 2, 3].includes(3, 5)", false),
+        ("[NaN].includes(NaN, 0)", true),
+    ] {
+        assert_eq!(
+            evaluator.evaluate(expr).unwrap(),
+            Value::Bool(expected),
+            "{}",
+            expr
+        );
+    }
 }
 
 #[test]
@@ -489,7 +504,7 @@ fn test_array_includes_arity_error() {
 
     let res_no_args = evaluator.evaluate("myArr.includes()");
     match res_no_args {
-        Err(EvaluationError::CustomFunction(CustomFuncError::ArityError { expected, got })) => {
+        Err(EvaluationError::CustomFunction(CustomFuncError::ArityError { expected, got }, _)) => {
             assert_eq!(expected, 1);
             assert_eq!(got, 0);
         }
@@ -499,11 +514,14 @@ fn test_array_includes_arity_error() {
         ),
     }
 
-    let res_many_args = evaluator.evaluate("myArr.includes(1, 2)");
+    // The optional `fromIndex` is accepted
+    assert_eq!(evaluator.evaluate("myArr.includes(1, 2)").unwrap(), false);
+
+    let res_many_args = evaluator.evaluate("myArr.includes(1, 2, 3)");
     match res_many_args {
-        Err(EvaluationError::CustomFunction(CustomFuncError::ArityError { expected, got })) => {
-            assert_eq!(expected, 1);
-            assert_eq!(got, 2);
+        Err(EvaluationError::CustomFunction(CustomFuncError::ArityError { expected, got }, _)) => {
+            assert_eq!(expected, 2);
+            assert_eq!(got, 3);
         }
         _ => panic!(
             "Expected ArityError for many arguments, got {:?}",
@@ -515,14 +533,14 @@ fn test_array_includes_arity_error() {
 #[test]
 fn test_array_includes_on_non_array() {
     let mut context = HashMap::new();
-    context.insert("notAnArray".to_string(), Value::String("hello".to_string()));
+    context.insert("notAnArray".to_string(), Value::Bool(true));
     let evaluator = Evaluator::new(context, HashMap::new());
 
     let result = evaluator.evaluate("notAnArray.includes(1)");
     match result {
-        Err(EvaluationError::TypeError(msg)) => {
-            // This error is from evaluate_dot_expr directly when trying to access 'includes' on a string "hello".
-            assert_eq!(msg, "Cannot read properties of null or primitive value: hello (trying to access property: includes)");
+        Err(EvaluationError::TypeError(msg, _)) => {
+            // This error is from evaluate_dot_expr directly when trying to access 'includes' on the boolean.
+            assert_eq!(msg, "Cannot read properties of null or primitive value: true (trying to access property: includes)");
         }
         _ => panic!(
             "Expected TypeError when calling .includes on non-array, got {:?}",
@@ -580,7 +598,7 @@ fn test_custom_adder_arity_error_few_args() {
 
     let result = evaluator.evaluate("custom_add(10)");
     match result {
-        Err(EvaluationError::CustomFunction(CustomFuncError::ArityError { expected, got })) => {
+        Err(EvaluationError::CustomFunction(CustomFuncError::ArityError { expected, got }, _)) => {
             assert_eq!(expected, 2);
             assert_eq!(got, 1);
         }
@@ -598,7 +616,7 @@ fn test_custom_adder_arity_error_many_args() {
 
     let result = evaluator.evaluate("custom_add(10, 20, 30)");
     match result {
-        Err(EvaluationError::CustomFunction(CustomFuncError::ArityError { expected, got })) => {
+        Err(EvaluationError::CustomFunction(CustomFuncError::ArityError { expected, got }, _)) => {
             assert_eq!(expected, 2);
             assert_eq!(got, 3);
         }
@@ -616,7 +634,7 @@ fn test_custom_adder_type_error_arg1() {
 
     let result = evaluator.evaluate("custom_add('not_a_number', 10)");
     match result {
-        Err(EvaluationError::CustomFunction(CustomFuncError::ArgumentError(msg))) => {
+        Err(EvaluationError::CustomFunction(CustomFuncError::ArgumentError(msg), _)) => {
             assert_eq!(msg, "Arguments must be numbers");
         }
         _ => panic!("Expected ArgumentError, got {:?}", result),
@@ -633,7 +651,7 @@ fn test_custom_adder_type_error_arg2() {
 
     let result = evaluator.evaluate("custom_add(10, 'not_a_number')");
     match result {
-        Err(EvaluationError::CustomFunction(CustomFuncError::ArgumentError(msg))) => {
+        Err(EvaluationError::CustomFunction(CustomFuncError::ArgumentError(msg), _)) => {
             assert_eq!(msg, "Arguments must be numbers");
         }
         _ => panic!("Expected ArgumentError, got {:?}", result),
@@ -680,10 +698,7 @@ fn test_array_length_direct() {
     let evaluator = Evaluator::new(context, HashMap::new());
 
     let result = evaluator.evaluate("myArray.length").unwrap();
-    assert_eq!(
-        result,
-        Value::Number(serde_json::Number::from_f64(3.0).unwrap())
-    );
+    assert_eq!(result, Value::Number(serde_json::Number::from(3)));
 }
 
 #[test]
@@ -697,10 +712,7 @@ fn test_array_length_nested_in_object() {
     let evaluator = Evaluator::new(context, HashMap::new());
 
     let result = evaluator.evaluate("myObj.arr.length").unwrap();
-    assert_eq!(
-        result,
-        Value::Number(serde_json::Number::from_f64(2.0).unwrap())
-    );
+    assert_eq!(result, Value::Number(serde_json::Number::from(2)));
 }
 
 #[test]
@@ -715,21 +727,13 @@ fn test_length_on_non_array() {
 
     let evaluator = Evaluator::new(context.clone(), HashMap::new());
 
-    // String.length is not yet implemented, should fall into the generic "cannot read props of primitive" or specific "length" error
-    let res_str = evaluator.evaluate("myString.length");
-    match res_str {
-        Err(EvaluationError::TypeError(msg)) => {
-            assert_eq!(
-                msg,
-                "Cannot read property 'length' of non-array/non-object value: hello"
-            );
-        }
-        _ => panic!("Expected TypeError for string.length, got {:?}", res_str),
-    }
+    // Strings report their UTF-16 length
+    let res_str = evaluator.evaluate("myString.length").unwrap();
+    assert_eq!(res_str.as_f64(), Some(5.0));
 
     let res_num = evaluator.evaluate("myNum.length");
     match res_num {
-        Err(EvaluationError::TypeError(msg)) => {
+        Err(EvaluationError::TypeError(msg, _)) => {
             assert_eq!(
                 msg,
                 "Cannot read property 'length' of non-array/non-object value: 123"
@@ -744,7 +748,7 @@ fn test_length_on_non_array() {
 
     let res_null = evaluator.evaluate("nullVar.length");
     match res_null {
-        Err(EvaluationError::TypeError(msg)) => {
+        Err(EvaluationError::TypeError(msg, _)) => {
             assert_eq!(
                 msg,
                 "Cannot read property 'length' of non-array/non-object value: null"
@@ -804,10 +808,10 @@ fn test_property_access_on_nested_object() {
     );
     assert_eq!(evaluator.evaluate("item.nested.foo").unwrap(), Value::Null);
 
-    let res_access_on_null = evaluator.evaluate("item.nonexistent.bar"); // item.nonexistent is Null, then .bar on Null
+    let res_access_on_null = evaluator.evaluate("item.nonexistent.bar"); // item.nonexistent is undefined, then .bar on undefined
     match res_access_on_null {
-        Err(EvaluationError::TypeError(msg)) => {
-            assert!(msg.contains("Cannot read properties of null or primitive value: null (trying to access property: bar)"));
+        Err(EvaluationError::TypeError(msg, _)) => {
+            assert!(msg.contains("Cannot read properties of null or primitive value: undefined (trying to access property: bar)"));
         }
         _ => panic!(
             "Expected TypeError for item.nonexistent.bar, got {:?}",
@@ -830,7 +834,7 @@ fn test_property_access_on_null_or_primitive_object_error() {
     for case in cases {
         let result = evaluator.evaluate(case);
         match result {
-            Err(EvaluationError::TypeError(msg)) => {
+            Err(EvaluationError::TypeError(msg, _)) => {
                 assert!(msg.starts_with("Cannot read properties of null or primitive value:"));
             }
             _ => panic!(
@@ -0,0 +1,17 @@
+//! Helpers shared by the integration tests.
+
+// Each test binary compiles its own copy and only uses some of the helpers
+#![allow(dead_code)]
+
+use exprimo::Evaluator;
+use std::collections::HashMap;
+
+/// Turns a JSON object such as `json!({ "a": 1 })` into an evaluation context.
+pub fn context(context: serde_json::Value) -> HashMap<String, serde_json::Value> {
+    context.as_object().unwrap().clone().into_iter().collect()
+}
+
+/// An evaluator over the variables of a JSON object, with no custom functions.
+pub fn evaluator_with(context: serde_json::Value) -> Evaluator {
+    Evaluator::new(self::context(context), HashMap::new())
+}
@@ -0,0 +1,141 @@
+use exprimo::{CompiledExpression, EvaluationError, Evaluator};
+use serde_json::Value;
+use std::collections::HashMap;
+
+fn assert_send_sync_clone<T: Send + Sync + Clone>() {}
+
+#[test]
+fn test_compiled_expression_is_send_sync_clone() {
+    assert_send_sync_clone::<CompiledExpression>();
+}
+
+#[test]
+fn test_compile_once_evaluate_many() {
+    let compiler = Evaluator::new(HashMap::new(), HashMap::new());
+    let compiled = compiler
+        .compile("user_age >= 18 && user_status === 'active'")
+        .unwrap();
+    assert_eq!(
+        compiled.source(),
+        "user_age >= 18 && user_status === 'active'"
+    );
+
+    let cases = vec![
+        (30, "active", true),
+        (16, "active", false),
+        (40, "banned", false),
+    ];
+    for (age, status, expected) in cases {
+        let mut context = HashMap::new();
+        context.insert("user_age".to_string(), Value::Number(age.into()));
+        context.insert("user_status".to_string(), Value::String(status.to_string()));
+        let evaluator = Evaluator::new(context, HashMap::new());
+
+        assert_eq!(
+            compiled.evaluate(&evaluator).unwrap(),
+            Value::Bool(expected)
+        );
+    }
+}
+
+#[test]
+fn test_compiled_expression_across_threads() {
+    let compiler = Evaluator::new(HashMap::new(), HashMap::new());
+    let compiled = compiler.compile("x * 2").unwrap();
+
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let compiled = compiled.clone();
+            std::thread::spawn(move || {
+                let mut context = HashMap::new();
+                context.insert("x".to_string(), Value::Number(i.into()));
+                let evaluator = Evaluator::new(context, HashMap::new());
+                compiled.evaluate(&evaluator).unwrap().as_f64()
+            })
+        })
+        .collect();
+
+    for (i, handle) in handles.into_iter().enumerate() {
+        assert_eq!(handle.join().unwrap(), Some(i as f64 * 2.0));
+    }
+}
+
+#[test]
+fn test_compile_reports_unsupported_syntax_up_front() {
+    let evaluator = Evaluator::new(HashMap::new(), HashMap::new());
+
+    // The unsupported branch is never taken at runtime, but compilation still rejects it.
+    let result = evaluator.compile("true ? 1 : (x = 2)");
+    match result {
+        Err(EvaluationError::Node(_)) => {}
+        _ => panic!("Expected Node error from compile, got {:?}", result),
+    }
+
+    let result = evaluator.compile("");
+    match result {
+        Err(EvaluationError::Node(_)) => {}
+        _ => panic!("Expected Node error for empty expression, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_missing_identifier_is_a_runtime_error() {
+    let evaluator = Evaluator::new(HashMap::new(), HashMap::new());
+
+    // Identifiers are resolved at evaluation time, so compiling succeeds.
+    let compiled = evaluator.compile("missing + 1").unwrap();
+    let result = compiled.evaluate(&evaluator);
+    match result {
+        Err(EvaluationError::Node(_)) => {}
+        _ => panic!(
+            "Expected Node error for missing identifier, got {:?}",
+            result
+        ),
+    }
+}
+
+#[test]
+fn test_compile_reports_parse_errors() {
+    let evaluator = Evaluator::new(HashMap::new(), HashMap::new());
+
+    for (expr, ranges) in [
+        ("a +* b", vec![(3, 4)]),
+        ("(a", vec![(2, 2)]),
+        ("a +", vec![(3, 3)]),
+        ("'abc", vec![(4, 4)]),
+        // Anything after the first expression is rejected rather than ignored
+        ("a; b", vec![(1, 4)]),
+        ("a b", vec![(2, 3)]),
+        ("a )  ", vec![(2, 3)]),
+        ("1 +* 2; 3", vec![(3, 4), (6, 9)]),
+    ] {
+        match evaluator.compile(expr) {
+            Err(EvaluationError::ParseError(diagnostics)) => {
+                let found: Vec<_> = diagnostics
+                    .iter()
+                    .map(|d| (u32::from(d.range().start()), u32::from(d.range().end())))
+                    .collect();
+                assert_eq!(found, ranges, "{}", expr);
+                assert!(diagnostics.iter().all(|d| !d.message().is_empty()));
+            }
+            other => panic!("Expected ParseError for {}, got {:?}", expr, other),
+        }
+    }
+
+    let message = evaluator.evaluate("a +* b").unwrap_err().to_string();
+    assert!(
+        message.contains("right hand side of a `+`") && message.contains("3..4"),
+        "{}",
+        message
+    );
+
+    // Trailing whitespace and comments are fine
+    for expr in ["1 + 2  ", "1 + 2 // total", "1 /* a */ + 2"] {
+        assert_eq!(
+            evaluator.evaluate(expr).unwrap(),
+            Value::from(3),
+            "{}",
+            expr
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use exprimo::{
+    Context, CustomFuncError, CustomFunction, EvaluationError, Evaluator, LayeredContext,
+};
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Debug)]
+struct Double;
+
+impl CustomFunction for Double {
+    fn call(&self, args: &[Value]) -> Result<Value, CustomFuncError> {
+        match args {
+            [Value::Number(n)] => Ok(Value::from(n.as_f64().unwrap() * 2.0)),
+            _ => Err(CustomFuncError::ArgumentError(
+                "Argument must be a number".to_string(),
+            )),
+        }
+    }
+}
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn test_evaluator_is_send_sync() {
+    assert_send_sync::<Evaluator>();
+}
+
+#[test]
+fn test_evaluate_with_per_call_context() {
+    let mut custom_functions: HashMap<String, Arc<dyn CustomFunction>> = HashMap::new();
+    custom_functions.insert("double".to_string(), Arc::new(Double));
+    let evaluator = Evaluator::with_functions(custom_functions);
+
+    for amount in [1, 5, 20] {
+        let mut context = HashMap::new();
+        context.insert("amount".to_string(), Value::from(amount));
+
+        let result = evaluator
+            .evaluate_with("double(amount) > 10", &context)
+            .unwrap();
+        assert_eq!(result, Value::Bool(amount * 2 > 10));
+    }
+}
+
+#[test]
+fn test_compiled_evaluate_with_shared_evaluator() {
+    let evaluator = Evaluator::default();
+    let compiled = evaluator.compile("user.tier === 'gold'").unwrap();
+
+    let mut gold = HashMap::new();
+    gold.insert("user".to_string(), serde_json::json!({ "tier": "gold" }));
+    let mut silver = HashMap::new();
+    silver.insert("user".to_string(), serde_json::json!({ "tier": "silver" }));
+
+    assert_eq!(
+        compiled.evaluate_with(&evaluator, &gold).unwrap(),
+        Value::Bool(true)
+    );
+    assert_eq!(
+        compiled.evaluate_with(&evaluator, &silver).unwrap(),
+        Value::Bool(false)
+    );
+}
+
+#[test]
+fn test_evaluate_with_ignores_constructor_context() {
+    let mut constructor_context = HashMap::new();
+    constructor_context.insert("a".to_string(), Value::from(1));
+    let evaluator = Evaluator::new(constructor_context, HashMap::new());
+
+    let per_call_context: HashMap<String, Value> = HashMap::new();
+    let result = evaluator.evaluate_with("a", &per_call_context);
+    match result {
+        Err(EvaluationError::Node(_)) => {}
+        _ => panic!(
+            "Expected Node error for missing identifier, got {:?}",
+            result
+        ),
+    }
+}
+
+#[derive(Debug)]
+struct Row {
+    columns: Vec<(&'static str, Value)>,
+    lookups: std::cell::Cell<usize>,
+}
+
+impl Context for Row {
+    fn get(&self, name: &str) -> Option<Cow<'_, Value>> {
+        self.lookups.set(self.lookups.get() + 1);
+        self.columns
+            .iter()
+            .find(|(column, _)| *column == name)
+            .map(|(_, value)| Cow::Borrowed(value))
+    }
+}
+
+#[test]
+fn test_custom_context_resolves_lazily() {
+    let row = Row {
+        columns: vec![
+            ("status", Value::String("shipped".to_string())),
+            ("total", Value::from(42)),
+        ],
+        lookups: std::cell::Cell::new(0),
+    };
+    let evaluator = Evaluator::default();
+
+    let result = evaluator
+        .evaluate_with("status === 'shipped' ? total : 0", &row)
+        .unwrap();
+    assert_eq!(result.as_f64(), Some(42.0));
+    // Only the identifiers the expression touches are looked up.
+    assert_eq!(row.lookups.get(), 2);
+
+    let result = evaluator.evaluate_with("missing", &row);
+    match result {
+        Err(EvaluationError::Node(_)) => {}
+        _ => panic!("Expected Node error for missing column, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_serde_json_map_context() {
+    let context = serde_json::json!({
+        "event": { "type": "signup" },
+        "count": 3
+    });
+    let context = context.as_object().unwrap();
+    let evaluator = Evaluator::default();
+
+    assert_eq!(
+        evaluator
+            .evaluate_with("event.type === 'signup' && count > 2", context)
+            .unwrap(),
+        Value::Bool(true)
+    );
+}
+
+#[test]
+fn test_layered_context_shadows_in_order() {
+    let mut locals = HashMap::new();
+    locals.insert("x".to_string(), Value::from(1));
+    let mut globals = std::collections::BTreeMap::new();
+    globals.insert("x".to_string(), Value::from(100));
+    globals.insert("y".to_string(), Value::from(10));
+
+    let context = LayeredContext::new().with(&locals).with(&globals);
+    let evaluator = Evaluator::default();
+
+    assert_eq!(
+        evaluator.evaluate_with("x + y", &context).unwrap().as_f64(),
+        Some(11.0)
+    );
+}
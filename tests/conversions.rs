@@ -0,0 +1,215 @@
+use exprimo::{CustomFuncError, CustomFunction, EvaluationError, Evaluator};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+mod common;
+use common::evaluator_with;
+
+/// Overrides the built-in `parseInt`.
+#[derive(Debug)]
+struct AlwaysSeven;
+
+impl CustomFunction for AlwaysSeven {
+    fn call(&self, _args: &[Value]) -> Result<Value, CustomFuncError> {
+        Ok(json!(7))
+    }
+}
+
+#[test]
+fn test_number_string_boolean() {
+    let evaluator = evaluator_with(json!({ "qty": "12", "flag": "" }));
+
+    for (expr, expected) in [
+        ("Number(qty) + 1", json!(13)),
+        ("Number('  4.5 ')", json!(4.5)),
+        ("Number(true)", json!(1)),
+        ("Number([])", json!(0)),
+        ("Number()", json!(0)),
+        ("Number('  ')", json!(0)),
+        ("Number('\\n\\t')", json!(0)),
+        ("Number('0x10')", json!(16)),
+        ("Number(' 0o17 ')", json!(15)),
+        ("Number('0b101')", json!(5)),
+        ("Number('.5') + Number('1e3')", json!(1000.5)),
+        ("'0x10' * 2", json!(32)),
+        ("Number('-Infinity') < 0", json!(true)),
+        ("Number('+Infinity') > 0", json!(true)),
+        ("String(42)", json!("42")),
+        ("String(1.5) + String(null)", json!("1.5null")),
+        ("String([1, [2, 3]])", json!("1,2,3")),
+        ("String()", json!("")),
+        ("Boolean(flag)", json!(false)),
+        ("Boolean('0')", json!(true)),
+        ("Boolean({})", json!(true)),
+        ("Boolean()", json!(false)),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+}
+
+#[test]
+fn test_number_rejects_non_javascript_spellings() {
+    let evaluator = evaluator_with(json!({}));
+
+    for input in [
+        "inf", "-inf", "infinity", "INFINITY", "nan", "NaN", "0x", "-0x10", "0xg", "1_000", "1e",
+        "1 2", ".",
+    ] {
+        let expr = format!("isNaN(Number('{}'))", input);
+        assert_eq!(evaluator.evaluate(&expr).unwrap(), json!(true), "{}", expr);
+    }
+}
+
+#[test]
+fn test_parse_int() {
+    let evaluator = evaluator_with(json!({}));
+
+    for (expr, expected) in [
+        ("parseInt('42px')", 42.0),
+        ("parseInt('  -17')", -17.0),
+        ("parseInt('3.99')", 3.0),
+        ("parseInt('0x1F')", 31.0),
+        ("parseInt('ff', 16)", 255.0),
+        ("parseInt('0xff', 16)", 255.0),
+        ("parseInt('101', 2)", 5.0),
+        ("parseInt('z', 36)", 35.0),
+        ("parseInt('12', 0)", 12.0),
+        ("parseInt(15.7)", 15.0),
+        ("Number.parseInt('8')", 8.0),
+    ] {
+        assert_eq!(
+            evaluator.evaluate(expr).unwrap().as_f64(),
+            Some(expected),
+            "{}",
+            expr
+        );
+    }
+}
+
+#[test]
+fn test_parse_float() {
+    let evaluator = evaluator_with(json!({}));
+
+    for (expr, expected) in [
+        ("parseFloat('2.75abc')", 2.75),
+        ("parseFloat('  .5')", 0.5),
+        ("parseFloat('-1.5e3x')", -1500.0),
+        ("parseFloat('2e')", 2.0),
+        ("parseFloat('5.')", 5.0),
+        ("Number.parseFloat('1_000')", 1.0),
+    ] {
+        assert_eq!(
+            evaluator.evaluate(expr).unwrap().as_f64(),
+            Some(expected),
+            "{}",
+            expr
+        );
+    }
+    assert_eq!(
+        evaluator
+            .evaluate("parseFloat('Infinity') > Number.MAX_SAFE_INTEGER")
+            .unwrap(),
+        Value::Bool(true)
+    );
+}
+
+#[test]
+fn test_is_nan_and_is_finite() {
+    let evaluator = evaluator_with(json!({ "input": "abc", "amount": "12.5" }));
+
+    for (expr, expected) in [
+        // The globals coerce their argument
+        ("isNaN(input)", true),
+        ("isNaN(amount)", false),
+        ("isNaN('')", false),
+        ("isFinite('12')", true),
+        ("isFinite(input)", false),
+        ("isFinite('Infinity')", false),
+        ("isFinite('infinity')", false),
+        ("isNaN('inf')", true),
+        ("isNaN('  ')", false),
+        // The `Number` functions do not
+        ("Number.isNaN(input)", false),
+        ("Number.isFinite('12')", false),
+        ("Number.isFinite(12)", true),
+    ] {
+        assert_eq!(
+            evaluator.evaluate(expr).unwrap(),
+            Value::Bool(expected),
+            "{}",
+            expr
+        );
+    }
+}
+
+#[test]
+fn test_number_statics() {
+    let evaluator = evaluator_with(json!({ "id": 9007199254740993u64 }));
+
+    for (expr, expected) in [
+        ("Number.isInteger(5)", true),
+        ("Number.isInteger(5.5)", false),
+        ("Number.isInteger('5')", false),
+        ("Number.isSafeInteger(Number.MAX_SAFE_INTEGER)", true),
+        ("Number.isSafeInteger(id)", false),
+        ("Math.abs(0.1 + 0.2 - 0.3) < Number.EPSILON", true),
+        ("Number.MIN_SAFE_INTEGER === -Number.MAX_SAFE_INTEGER", true),
+    ] {
+        assert_eq!(
+            evaluator.evaluate(expr).unwrap(),
+            Value::Bool(expected),
+            "{}",
+            expr
+        );
+    }
+    assert_eq!(
+        evaluator.evaluate("Number.MAX_SAFE_INTEGER").unwrap(),
+        json!(9007199254740991u64)
+    );
+    assert_eq!(evaluator.evaluate("Number.nope").unwrap(), Value::Null);
+}
+
+#[test]
+fn test_global_function_typeof_and_overrides() {
+    let evaluator = evaluator_with(json!({}));
+    assert_eq!(
+        evaluator.evaluate("typeof parseInt").unwrap(),
+        json!("function")
+    );
+    assert_eq!(
+        evaluator.evaluate("typeof Number").unwrap(),
+        json!("function")
+    );
+
+    // Custom functions take precedence over the built-ins
+    let mut custom_functions: HashMap<String, Arc<dyn CustomFunction>> = HashMap::new();
+    custom_functions.insert("parseInt".to_string(), Arc::new(AlwaysSeven));
+    let evaluator = Evaluator::new(HashMap::new(), custom_functions);
+    assert_eq!(evaluator.evaluate("parseInt('1')").unwrap(), json!(7));
+}
+
+#[test]
+fn test_global_function_argument_errors() {
+    let evaluator = evaluator_with(json!({}));
+
+    let result = evaluator.evaluate("String(1, 2)");
+    match result {
+        Err(EvaluationError::CustomFunction(CustomFuncError::ArityError { expected, got }, _)) => {
+            assert_eq!((expected, got), (1, 2));
+        }
+        _ => panic!("Expected ArityError, got {:?}", result),
+    }
+
+    let result = evaluator.evaluate("Number(/a/)");
+    match result {
+        Err(EvaluationError::TypeError(_, _)) => {}
+        _ => panic!("Expected TypeError, got {:?}", result),
+    }
+
+    // A function converts like any other value
+    assert_eq!(
+        evaluator.evaluate("isNaN(Number(x => x))").unwrap(),
+        json!(true)
+    );
+}
@@ -0,0 +1,190 @@
+use exprimo::{
+    Argument, CustomFuncError, CustomFunction, Decimal, Evaluator, RoundingStrategy, Value,
+};
+use serde_json::json;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+mod common;
+
+fn decimal_evaluator_with(context: serde_json::Value, scale: u32) -> Evaluator {
+    common::evaluator_with(context).with_decimal(scale, RoundingStrategy::MidpointNearestEven)
+}
+
+fn decimal(s: &str) -> Value {
+    Value::from(Decimal::from_str(s).unwrap())
+}
+
+#[test]
+fn test_decimal_arithmetic() {
+    let evaluator = decimal_evaluator_with(
+        json!({ "price": 19.99, "qty": 3, "discount": 0.15, "rate": 0.1 }),
+        10,
+    );
+
+    for (expr, expected) in [
+        ("0.1 + 0.2", "0.3"),
+        ("0.3 - 0.1", "0.2"),
+        ("1.1 * 1.1", "1.21"),
+        ("price * qty", "59.97"),
+        ("price * qty * (1 - discount)", "50.9745"),
+        ("rate + 0.2", "0.3"),
+        ("1 / 3", "0.3333333333"),
+        ("2 / 3", "0.6666666667"),
+        ("5.5 % 2", "1.5"),
+        ("-price", "-19.99"),
+        ("1e-3 + 1", "1.001"),
+    ] {
+        assert_eq!(
+            evaluator.evaluate_value(expr).unwrap(),
+            decimal(expected),
+            "{}",
+            expr
+        );
+    }
+
+    for (expr, expected) in [
+        ("0.1 + 0.2 === 0.3", json!(true)),
+        ("0.1 + 0.2 == '0.3'", json!(true)),
+        ("0.1 + 0.2 > 0.3", json!(false)),
+        ("0.1 + 0.2 <= 0.3", json!(true)),
+        ("price * 100 >= 1999", json!(true)),
+        ("String(0.1 + 0.2)", json!("0.3")),
+        ("`${price * qty}`", json!("59.97")),
+        // Whole results are plain integers
+        ("0.5 + 0.5", json!(1)),
+        ("price * 100", json!(1999)),
+        ("7 / 2", json!(3.5)),
+        ("Math.round(price)", json!(20)),
+        (
+            "[0.1, 0.2, 0.3].reduce((sum, x) => sum + x, 0) === 0.6",
+            json!(true),
+        ),
+        // Values a decimal cannot hold fall back to floating point
+        ("1 / 0", json!(null)),
+        ("isNaN(0 / 0)", json!(true)),
+        ("1e300 * 10", json!(1e301)),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+}
+
+#[test]
+fn test_decimal_scale_and_rounding() {
+    let evaluator = decimal_evaluator_with(json!({ "price": 2.675 }), 2);
+    for (expr, expected) in [
+        ("price * 1", "2.68"),
+        ("0.125 + 0", "0.12"),
+        ("10 / 3", "3.33"),
+        // Only the final result is rounded, so intermediate steps keep full precision
+        ("1 / 3 * 3", "1"),
+        ("100 * (1 / 3)", "33.33"),
+        ("0.125", "0.12"),
+    ] {
+        assert_eq!(
+            evaluator.evaluate_value(expr).unwrap(),
+            decimal(expected),
+            "{}",
+            expr
+        );
+    }
+
+    // Numbers inside arrays and objects are rounded too
+    assert_eq!(
+        evaluator.evaluate("[1 / 3, { total: 10 / 3 }]").unwrap(),
+        json!([0.33, { "total": 3.33 }])
+    );
+
+    let evaluator = Evaluator::default().with_decimal(2, RoundingStrategy::MidpointAwayFromZero);
+    assert_eq!(
+        evaluator.evaluate_value("0.125 + 0").unwrap(),
+        decimal("0.13")
+    );
+    let evaluator = Evaluator::default().with_decimal(0, RoundingStrategy::ToZero);
+    assert_eq!(evaluator.evaluate("7 / 2").unwrap(), json!(3));
+}
+
+#[derive(Debug)]
+struct AddTenth;
+
+impl CustomFunction for AddTenth {
+    fn call(&self, _args: &[serde_json::Value]) -> Result<serde_json::Value, CustomFuncError> {
+        Err(CustomFuncError::ArgumentError(
+            "expected a function".to_string(),
+        ))
+    }
+
+    fn call_with_lambdas(
+        &self,
+        args: &[Argument<'_>],
+    ) -> Result<serde_json::Value, CustomFuncError> {
+        let lambda = args[0].as_lambda().unwrap();
+        Ok(lambda.call(&[json!(0.1)])?)
+    }
+}
+
+#[test]
+fn test_parsed_and_lambda_numbers_are_decimals() {
+    let mut custom_functions: HashMap<String, Arc<dyn CustomFunction>> = HashMap::new();
+    custom_functions.insert("withTenth".to_string(), Arc::new(AddTenth));
+    let evaluator = Evaluator::new(HashMap::new(), custom_functions)
+        .with_decimal(10, RoundingStrategy::MidpointNearestEven);
+
+    for (expr, expected) in [
+        ("JSON.parse('0.1') + 0.2 === 0.3", json!(true)),
+        ("JSON.parse('{\"price\": 0.1}').price * 3", json!(0.3)),
+        ("withTenth(x => x + 0.2 === 0.3)", json!(true)),
+        ("withTenth(x => x * 3)", json!(0.3)),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+}
+
+#[test]
+fn test_floats_without_decimal_mode() {
+    let evaluator = Evaluator::default();
+    assert_eq!(
+        evaluator.evaluate("0.1 + 0.2").unwrap(),
+        json!(0.30000000000000004)
+    );
+    assert!(evaluator.evaluate_value("0.1").unwrap().as_f64().is_some());
+    assert_eq!(
+        evaluator.evaluate("0.1 + 0.2 === 0.3").unwrap(),
+        json!(false)
+    );
+}
+
+#[test]
+fn test_compiled_expression_follows_evaluator_mode() {
+    let compiled = Evaluator::default().compile("0.1 * 3").unwrap();
+
+    let decimal_evaluator =
+        Evaluator::default().with_decimal(10, RoundingStrategy::MidpointNearestEven);
+    assert_eq!(
+        compiled.evaluate_value(&decimal_evaluator).unwrap(),
+        decimal("0.3")
+    );
+    assert_eq!(
+        compiled.evaluate(&Evaluator::default()).unwrap(),
+        json!(0.30000000000000004)
+    );
+}
+
+#[cfg(feature = "arbitrary_precision")]
+#[test]
+fn test_context_digits_are_kept() {
+    let context: HashMap<String, serde_json::Value> =
+        serde_json::from_str(r#"{ "balance": 12345678901234567.89 }"#).unwrap();
+    let evaluator = Evaluator::new(context, HashMap::new())
+        .with_decimal(2, RoundingStrategy::MidpointNearestEven);
+
+    assert_eq!(
+        evaluator.evaluate_value("balance + 0.01").unwrap(),
+        decimal("12345678901234567.9")
+    );
+    assert_eq!(
+        evaluator.evaluate("balance - 0.09").unwrap().to_string(),
+        "12345678901234567.8"
+    );
+}
@@ -0,0 +1,127 @@
+use exprimo::{
+    CustomFuncError, CustomFunction, EvaluationError, Evaluator, NonJsonPolicy, TextRange,
+};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+mod common;
+use common::evaluator_with;
+
+/// The source text an error points at.
+fn failing_code<'a>(expr: &'a str, err: &EvaluationError) -> &'a str {
+    let range = err.range().expect("expected a location");
+    &expr[usize::from(range.start())..usize::from(range.end())]
+}
+
+#[derive(Debug)]
+struct Fail;
+
+impl CustomFunction for Fail {
+    fn call(&self, _args: &[serde_json::Value]) -> Result<serde_json::Value, CustomFuncError> {
+        Err(CustomFuncError::Generic("nope".to_string()))
+    }
+}
+
+#[test]
+fn test_errors_point_at_the_failing_sub_expression() {
+    let mut custom_functions: HashMap<String, Arc<dyn CustomFunction>> = HashMap::new();
+    custom_functions.insert("fail".to_string(), Arc::new(Fail));
+    let context = HashMap::from([
+        (
+            "user".to_string(),
+            json!({ "profile": null, "tags": ["a"] }),
+        ),
+        ("id".to_string(), json!(9007199254740993u64)),
+    ]);
+    let evaluator = Evaluator::new(context, custom_functions);
+
+    for (expr, code) in [
+        ("user.profile.name == 'x'", "user.profile.name"),
+        ("1 + missing * 2", "missing"),
+        ("user.tags.map(t => t.x.y).length", "t.x.y"),
+        ("[1, 2].includes()", "[1, 2].includes()"),
+        ("true && fail(1)", "fail(1)"),
+        ("JSON.parse('{')", "JSON.parse('{')"),
+        ("!undefinedFn()", "undefinedFn"),
+    ] {
+        let err = evaluator.evaluate(expr).unwrap_err();
+        assert_eq!(failing_code(expr, &err), code, "{}: {}", expr, err);
+    }
+
+    // Lambdas report the location inside their body
+    let err = evaluator
+        .evaluate("user.tags.some(t => t.toFixed(2))")
+        .unwrap_err();
+    assert!(matches!(err, EvaluationError::TypeError(_, Some(_))));
+
+    // Conversion errors point at the whole expression
+    let evaluator = Evaluator::default().with_non_json_policy(NonJsonPolicy::Error);
+    let err = evaluator.evaluate(" 0 / 0").unwrap_err();
+    assert_eq!(failing_code(" 0 / 0", &err), "0 / 0");
+}
+
+#[test]
+fn test_error_line_and_column() {
+    let evaluator = evaluator_with(json!({ "order": { "items": null } }));
+
+    let expr = "order.items !== 1 &&\n  order.items.length > 0";
+    let location = evaluator.evaluate(expr).unwrap_err().location().unwrap();
+    assert_eq!((location.line(), location.column()), (2, 3));
+    assert_eq!(location.to_string(), "2:3");
+
+    // Columns count characters, not bytes
+    let expr = "'é' + order.items.length";
+    let location = evaluator.evaluate(expr).unwrap_err().location().unwrap();
+    assert_eq!(location.column(), 7);
+
+    let err = evaluator.compile("a +\n  * b").unwrap_err();
+    let location = err.location().unwrap();
+    assert_eq!((location.line(), location.column()), (2, 3));
+    assert_eq!(location.range(), TextRange::new(6.into(), 7.into()));
+}
+
+#[test]
+fn test_render() {
+    let evaluator = evaluator_with(json!({ "user": { "profile": null } }));
+
+    let expr = "user.profile.name";
+    let rendered = evaluator.evaluate(expr).unwrap_err().render(expr);
+    assert_eq!(
+        rendered,
+        "error: Type error: Cannot read properties of null or primitive value: null (trying to access property: name)\n\
+         \x20--> 1:1\n\
+         \x20 |\n\
+         1 | user.profile.name\n\
+         \x20 | ^^^^^^^^^^^^^^^^^\n"
+    );
+
+    let expr = "user.profile === null &&\n  missing";
+    let rendered = evaluator.evaluate(expr).unwrap_err().render(expr);
+    assert!(
+        rendered.ends_with(" --> 2:3\n  |\n2 |   missing\n  |   ^^^^^^^\n"),
+        "{}",
+        rendered
+    );
+
+    // Every parse diagnostic is shown, and empty ranges get a single caret
+    let expr = "1 +* 2; 3 +";
+    let rendered = evaluator.compile(expr).unwrap_err().render(expr);
+    assert_eq!(rendered.matches("error: ").count(), 2, "{}", rendered);
+    assert!(rendered.contains("  |    ^\n"), "{}", rendered);
+    assert!(rendered.contains("  |       ^^^^^\n"), "{}", rendered);
+
+    // The location is left to `render`, not repeated in the message
+    let err = evaluator.evaluate("missing").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Node evaluation failed: Identifier 'missing' not found in context."
+    );
+
+    // A mismatched source does not panic
+    let err = evaluator
+        .evaluate("user.profile === null &&\n  missing")
+        .unwrap_err();
+    assert!(err.render("").starts_with("error: "));
+    assert!(err.render("x").starts_with("error: "));
+}
@@ -0,0 +1,232 @@
+use exprimo::{BigInt, EvaluationError, Evaluator, UnsafeIntegerPolicy, Value};
+use serde_json::json;
+use std::collections::HashMap;
+
+mod common;
+use common::evaluator_with;
+
+#[test]
+fn test_integer_arithmetic() {
+    let evaluator = evaluator_with(json!({ "price": 2.5, "qty": 3, "whole": 4.0 }));
+
+    for (expr, expected) in [
+        ("1 + 1", json!(2)),
+        ("7 - 10", json!(-3)),
+        ("6 * 7", json!(42)),
+        ("6 / 3", json!(2)),
+        ("7 % 3", json!(1)),
+        ("-7 % 3", json!(-1)),
+        ("2 ** 10", json!(1024)),
+        ("qty * 2", json!(6)),
+        // Fractional results stay floats
+        ("7 / 2", json!(3.5)),
+        ("2 ** -1", json!(0.5)),
+        ("price * qty", json!(7.5)),
+        // Whole results are integers whichever way they were computed
+        ("price * 2", json!(5)),
+        ("whole + 1", json!(5)),
+        ("[1, 2, 3].length", json!(3)),
+        ("Math.floor(2.7)", json!(2)),
+        ("'5' * '2'", json!(10)),
+        ("true + 1", json!(2)),
+        // Literals in other notations
+        ("1e3", json!(1000)),
+        ("0x1F", json!(31)),
+        ("0b101", json!(5)),
+        ("1_000_000", json!(1000000)),
+        ("017", json!(15)),
+        ("0.5", json!(0.5)),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+
+    // Context floats that are whole are passed through unchanged
+    assert!(evaluator.evaluate("whole").unwrap().is_f64());
+}
+
+#[test]
+fn test_integer_arithmetic_keeps_negative_zero() {
+    let evaluator = evaluator_with(json!({}));
+
+    for expr in ["0 * -1", "-0", "-4 % 2", "0 / -5"] {
+        let result = evaluator.evaluate_value(expr).unwrap();
+        let n = result.as_f64().unwrap();
+        assert!(n == 0.0 && n.is_sign_negative(), "{}", expr);
+    }
+}
+
+#[test]
+fn test_64_bit_integers_are_exact() {
+    let evaluator = evaluator_with(json!({
+        "max": u64::MAX,
+        "min": i64::MIN,
+        "order_id": 9007199254740993u64
+    }));
+
+    for (expr, expected) in [
+        ("order_id === 9007199254740993", json!(true)),
+        ("order_id === 9007199254740992", json!(false)),
+        ("order_id > 9007199254740992", json!(true)),
+        ("order_id + 1", json!(9007199254740994u64)),
+        ("order_id - 2", json!(9007199254740991u64)),
+        ("order_id * 2", json!(18014398509481986u64)),
+        ("order_id % 10", json!(3)),
+        ("-order_id", json!(-9007199254740993i64)),
+        ("max - 1", json!(u64::MAX - 1)),
+        ("min + 1", json!(i64::MIN + 1)),
+        ("String(order_id)", json!("9007199254740993")),
+        ("`#${order_id}`", json!("#9007199254740993")),
+        ("[order_id].includes(9007199254740993)", json!(true)),
+        ("2 ** 60", json!(1u64 << 60)),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+
+    // Results beyond 64 bits fall back to floats, as in JavaScript
+    assert_eq!(
+        evaluator.evaluate("max + 1").unwrap().as_f64(),
+        Some(18446744073709551616.0)
+    );
+}
+
+#[test]
+fn test_unsafe_integer_policy() {
+    let context = HashMap::from([
+        ("order_id".to_string(), json!(9007199254740993u64)),
+        ("small".to_string(), json!(42)),
+    ]);
+    let evaluator = Evaluator::new(context, HashMap::new())
+        .with_unsafe_integer_policy(UnsafeIntegerPolicy::Error);
+
+    // Exact operations are still allowed
+    for (expr, expected) in [
+        ("order_id === 9007199254740993", json!(true)),
+        ("order_id + 1", json!(9007199254740994u64)),
+        ("order_id / 1", json!(9007199254740993u64)),
+        ("small / 5", json!(8.4)),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+
+    for (expr, value) in [
+        ("order_id + 0.5", "9007199254740993"),
+        ("order_id / 2", "9007199254740993"),
+        ("Math.max(order_id, 1)", "9007199254740993"),
+        ("order_id > 1.5", "9007199254740993"),
+        ("order_id * order_id", "81129638414606699710187514626049"),
+        ("3 ** 41", "36472996377170786403"),
+        ("3 ** 100", "5.153775207320113e+47"),
+    ] {
+        match evaluator.evaluate(expr) {
+            Err(EvaluationError::UnsafeInteger(found, _)) => assert_eq!(found, value, "{}", expr),
+            result => panic!("Expected UnsafeInteger for {}, got {:?}", expr, result),
+        }
+    }
+
+    // The default policy rounds, as JavaScript does
+    let evaluator = evaluator_with(json!({ "order_id": 9007199254740993u64 }));
+    assert_eq!(
+        evaluator.evaluate("order_id + 0.5").unwrap(),
+        json!(9007199254740992.0)
+    );
+}
+
+#[test]
+fn test_bigint() {
+    let context = HashMap::from([("order_id".to_string(), json!(9007199254740993u64))]);
+    let evaluator = Evaluator::new(context, HashMap::new()).with_bigint();
+
+    for (expr, expected) in [
+        ("1n + 2n", json!(3)),
+        ("7n / 2n", json!(3)),
+        ("-7n % 2n", json!(-1)),
+        ("2n ** 64n - 1n", json!(u64::MAX)),
+        ("BigInt(order_id) * 2n", json!(18014398509481986u64)),
+        ("BigInt('123')", json!(123)),
+        ("BigInt(true)", json!(1)),
+        (
+            "String(2n ** 100n)",
+            json!("1267650600228229401496703205376"),
+        ),
+        ("`${10n}`", json!("10")),
+        ("typeof 1n", json!("bigint")),
+        ("typeof BigInt", json!("function")),
+        ("1n === 1n", json!(true)),
+        ("1n === 1", json!(false)),
+        ("1n == 1", json!(true)),
+        ("1n == '1'", json!(true)),
+        ("2n > 1", json!(true)),
+        ("1n < 1.5", json!(true)),
+        ("BigInt(order_id) === 9007199254740993n", json!(true)),
+        ("0n ? 'yes' : 'no'", json!("no")),
+        ("Number(5n) + 1", json!(6)),
+        ("[1n, 2n].includes(2n)", json!(true)),
+        ("1n & 1n", json!(1)),
+        ("1n | 2n", json!(3)),
+        ("6n ^ 3n", json!(5)),
+        ("1n << 2n", json!(4)),
+        ("-5n >> 1n", json!(-3)),
+        ("8n >> -1n", json!(16)),
+        ("-1n >> 100n", json!(-1)),
+        ("~1n", json!(-2)),
+        ("~-1n", json!(0)),
+        ("-6n & 0xffn", json!(250)),
+        ("String((2n ** 64n) | 1n)", json!("18446744073709551617")),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+
+    let big: BigInt = "1267650600228229401496703205376".parse().unwrap();
+    assert_eq!(
+        evaluator.evaluate_value("2n ** 100n").unwrap(),
+        Value::BigInt(big)
+    );
+
+    for expr in [
+        "1n + 1",
+        "1n * 1.5",
+        "+1n",
+        "Math.abs(-1n)",
+        "1n / 0n",
+        "2n ** -1n",
+        "BigInt(1.5)",
+        "BigInt('1.5')",
+        "BigInt(null)",
+        "JSON.stringify({ id: 1n })",
+        "2n ** 10000000n",
+        "1n & 1",
+        "1 | 1n",
+        "~1n + 1",
+        "1n >>> 0n",
+        "1n << 10000000n",
+    ] {
+        let result = evaluator.evaluate(expr);
+        assert!(
+            matches!(result, Err(EvaluationError::TypeError(_, _))),
+            "Expected TypeError for {}, got {:?}",
+            expr,
+            result
+        );
+    }
+}
+
+#[test]
+fn test_bigint_requires_opt_in() {
+    let evaluator = Evaluator::default();
+
+    let result = evaluator.evaluate("1n + 2n");
+    match result {
+        Err(EvaluationError::TypeError(message, _)) => {
+            assert!(message.contains("BigInt literal '1n'"), "{}", message)
+        }
+        _ => panic!("Expected TypeError, got {:?}", result),
+    }
+    assert!(matches!(
+        evaluator.evaluate("BigInt(1)"),
+        Err(EvaluationError::Node(_))
+    ));
+    assert_eq!(
+        evaluator.evaluate("typeof BigInt").unwrap(),
+        json!("undefined")
+    );
+}
@@ -0,0 +1,105 @@
+use exprimo::EvaluationError;
+use serde_json::{json, Value};
+
+mod common;
+use common::evaluator_with;
+
+#[test]
+fn test_json_parse() {
+    let evaluator = evaluator_with(json!({
+        "event": { "payload": "{\"user\":{\"id\":7,\"roles\":[\"admin\"]}}" }
+    }));
+
+    assert_eq!(
+        evaluator
+            .evaluate("JSON.parse(event.payload).user.id")
+            .unwrap(),
+        json!(7)
+    );
+    assert_eq!(
+        evaluator
+            .evaluate("JSON.parse(event.payload).user.roles.includes('admin')")
+            .unwrap(),
+        Value::Bool(true)
+    );
+    for (expr, expected) in [
+        ("JSON.parse('[1, \"a\", null]')", json!([1, "a", null])),
+        ("JSON.parse(' true ')", json!(true)),
+        ("JSON.parse('\"text\"')", json!("text")),
+        ("JSON.parse(42)", json!(42)),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+}
+
+#[test]
+fn test_json_parse_errors() {
+    let evaluator = evaluator_with(json!({ "bad": "{\"a\": 1,}" }));
+
+    for expr in ["JSON.parse(bad)", "JSON.parse('')", "JSON.parse('{a: 1}')"] {
+        let result = evaluator.evaluate(expr);
+        match result {
+            Err(EvaluationError::JsonParse(err, _)) => assert!(err.is_syntax() || err.is_eof()),
+            _ => panic!("Expected JsonParse error for {}, got {:?}", expr, result),
+        }
+    }
+
+    let err = evaluator.evaluate("JSON.parse(bad)").unwrap_err();
+    assert!(err.to_string().starts_with("JSON.parse failed"), "{}", err);
+}
+
+#[test]
+fn test_json_stringify() {
+    let evaluator = evaluator_with(json!({
+        "order": { "id": 1, "items": [{ "qty": 2, "sku": "a" }], "note": null }
+    }));
+
+    for (expr, expected) in [
+        (
+            "JSON.stringify(order)",
+            r#"{"id":1,"items":[{"qty":2,"sku":"a"}],"note":null}"#,
+        ),
+        // Computed numbers are written like JavaScript numbers
+        ("JSON.stringify([1 + 1, 0.5, -3])", "[2,0.5,-3]"),
+        ("JSON.stringify('a\"b\\n')", r#""a\"b\n""#),
+        ("JSON.stringify(null)", "null"),
+        ("JSON.stringify({})", "{}"),
+        ("JSON.stringify([[]], null, 2)", "[\n  []\n]"),
+        (
+            "JSON.stringify({ a: [1, 2], b: {} }, null, 2)",
+            "{\n  \"a\": [\n    1,\n    2\n  ],\n  \"b\": {}\n}",
+        ),
+        ("JSON.stringify([1], null, '\\t')", "[\n\t1\n]"),
+        (
+            "JSON.stringify({ a: 1, b: 2, c: 3 }, ['a', 'c'])",
+            r#"{"a":1,"c":3}"#,
+        ),
+    ] {
+        assert_eq!(
+            evaluator.evaluate(expr).unwrap(),
+            json!(expected),
+            "{}",
+            expr
+        );
+    }
+
+    // Round trip
+    assert_eq!(
+        evaluator
+            .evaluate("JSON.parse(JSON.stringify(order))")
+            .unwrap(),
+        json!({ "id": 1, "items": [{ "qty": 2, "sku": "a" }], "note": null })
+    );
+}
+
+#[test]
+fn test_json_namespace() {
+    let evaluator = evaluator_with(json!({}));
+    assert_eq!(evaluator.evaluate("typeof JSON").unwrap(), json!("object"));
+
+    let result = evaluator.evaluate("JSON.stringify({}, x => x)");
+    match result {
+        Err(EvaluationError::TypeError(_, _)) => {}
+        _ => panic!("Expected TypeError, got {:?}", result),
+    }
+}
@@ -0,0 +1,251 @@
+use exprimo::{
+    Argument, CustomFuncError, CustomFunction, EvaluationError, Evaluator, NonJsonPolicy,
+};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+mod common;
+
+/// `anyOf(list, predicate)`, a stand-in for `Array.prototype.some`.
+#[derive(Debug)]
+struct AnyOf;
+
+impl CustomFunction for AnyOf {
+    fn call(&self, _args: &[Value]) -> Result<Value, CustomFuncError> {
+        Err(CustomFuncError::ArgumentError(
+            "anyOf expects a predicate".to_string(),
+        ))
+    }
+
+    fn call_with_lambdas(&self, args: &[Argument<'_>]) -> Result<Value, CustomFuncError> {
+        match args {
+            [Argument::Value(Value::Array(items)), Argument::Lambda(predicate)] => {
+                for (i, item) in items.iter().enumerate() {
+                    if predicate.call(&[item.clone(), Value::from(i)])? == Value::Bool(true) {
+                        return Ok(Value::Bool(true));
+                    }
+                }
+                Ok(Value::Bool(false))
+            }
+            _ => Err(CustomFuncError::ArgumentError(
+                "anyOf expects an array and a predicate".to_string(),
+            )),
+        }
+    }
+}
+
+/// `apply(f, ...args)` calls `f` with the remaining arguments.
+#[derive(Debug)]
+struct Apply;
+
+impl CustomFunction for Apply {
+    fn call(&self, _args: &[Value]) -> Result<Value, CustomFuncError> {
+        Err(CustomFuncError::ArgumentError(
+            "apply expects a function".to_string(),
+        ))
+    }
+
+    fn call_with_lambdas(&self, args: &[Argument<'_>]) -> Result<Value, CustomFuncError> {
+        let (first, rest) = args.split_first().unwrap();
+        let lambda = first.as_lambda().unwrap();
+        let values: Vec<Value> = rest
+            .iter()
+            .map(|arg| arg.as_value().cloned().unwrap())
+            .collect();
+        Ok(json!({ "arity": lambda.arity(), "result": lambda.call(&values)? }))
+    }
+}
+
+/// Only implements `call`, so it rejects arrow functions.
+#[derive(Debug)]
+struct Identity;
+
+impl CustomFunction for Identity {
+    fn call(&self, args: &[Value]) -> Result<Value, CustomFuncError> {
+        Ok(args.first().cloned().unwrap_or(Value::Null))
+    }
+}
+
+fn evaluator_with(context: Value) -> Evaluator {
+    let context = common::context(context);
+    let mut custom_functions: HashMap<String, Arc<dyn CustomFunction>> = HashMap::new();
+    custom_functions.insert("anyOf".to_string(), Arc::new(AnyOf));
+    custom_functions.insert("apply".to_string(), Arc::new(Apply));
+    custom_functions.insert("identity".to_string(), Arc::new(Identity));
+    Evaluator::new(context, custom_functions)
+}
+
+#[test]
+fn test_lambda_passed_to_custom_function() {
+    let evaluator = evaluator_with(json!({
+        "orders": [{ "total": 40 }, { "total": 150 }]
+    }));
+
+    assert_eq!(
+        evaluator
+            .evaluate("anyOf(orders, o => o.total > 100)")
+            .unwrap(),
+        Value::Bool(true)
+    );
+    assert_eq!(
+        evaluator
+            .evaluate("anyOf(orders, (o) => o.total > 500)")
+            .unwrap(),
+        Value::Bool(false)
+    );
+    // Extra arguments (here the index) are ignored by a one-parameter lambda, and
+    // missing ones are undefined
+    assert_eq!(
+        evaluator
+            .evaluate("anyOf(orders, (o, i, extra) => i === 1 && extra === undefined)")
+            .unwrap(),
+        Value::Bool(true)
+    );
+}
+
+#[test]
+fn test_lambda_parameters_and_arity() {
+    let evaluator = evaluator_with(json!({}));
+
+    assert_eq!(
+        evaluator.evaluate("apply((a, b) => a * b, 6, 7)").unwrap(),
+        json!({ "arity": 2, "result": 42 })
+    );
+    assert_eq!(
+        evaluator.evaluate("apply(() => 'none')").unwrap(),
+        json!({ "arity": 0, "result": "none" })
+    );
+    assert_eq!(
+        evaluator
+            .evaluate("apply(x => ({ wrapped: x }), 1)")
+            .unwrap(),
+        json!({ "arity": 1, "result": { "wrapped": 1 } })
+    );
+}
+
+#[test]
+fn test_lambda_lexical_scope() {
+    let evaluator = evaluator_with(json!({
+        "threshold": 100,
+        "x": "outer",
+        "orders": [
+            { "featured": "a", "items": [{ "sku": "b" }, { "sku": "a" }] }
+        ]
+    }));
+
+    // The body sees the surrounding context
+    assert_eq!(
+        evaluator
+            .evaluate("apply(total => total > threshold, 150)")
+            .unwrap()["result"],
+        Value::Bool(true)
+    );
+    // Parameters shadow context variables
+    assert_eq!(
+        evaluator.evaluate("apply(x => x, 'inner')").unwrap()["result"],
+        json!("inner")
+    );
+    // Nested lambdas see the parameters of enclosing ones
+    assert_eq!(
+        evaluator
+            .evaluate("anyOf(orders, o => anyOf(o.items, item => item.sku === o.featured))")
+            .unwrap(),
+        Value::Bool(true)
+    );
+}
+
+#[test]
+fn test_lambda_errors_propagate() {
+    let evaluator = evaluator_with(json!({ "list": [1] }));
+
+    let result = evaluator.evaluate("anyOf(list, n => missing > n)");
+    match result {
+        Err(EvaluationError::Node(err)) => {
+            assert!(err.to_string().contains("Identifier 'missing' not found"));
+        }
+        _ => panic!(
+            "Expected Node error from inside the lambda, got {:?}",
+            result
+        ),
+    }
+}
+
+#[test]
+fn test_lambda_rejected_by_value_only_function() {
+    let evaluator = evaluator_with(json!({}));
+
+    let result = evaluator.evaluate("identity(x => x)");
+    match result {
+        Err(EvaluationError::CustomFunction(CustomFuncError::ArgumentError(_), _)) => {}
+        _ => panic!("Expected ArgumentError, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_functions_are_values() {
+    let evaluator = evaluator_with(json!({ "list": [1, 2] }));
+
+    for (expr, expected) in [
+        ("(x => x)(1)", json!(1)),
+        ("[x => x * 2][0](3)", json!(6)),
+        ("{ f: x => x + 1 }.f(1)", json!(2)),
+        ("(true ? x => x : x => 2)(1)", json!(1)),
+        ("[1].map(true ? x => x : x => 2)", json!([1])),
+        // Parentheses around an argument are fine
+        ("list.map((x => x * 2))", json!([2, 4])),
+        // A parameter can hold a function and be called
+        ("(f => f(2))(x => x * 3)", json!(6)),
+        ("[x => x * 2, x => x + 1].map(f => f(3))", json!([6, 4])),
+        // Functions remember the parameters in scope where they were written
+        ("(a => b => a + b)(1)(2)", json!(3)),
+        ("list.map(x => () => x).map(f => f())", json!([1, 2])),
+        ("typeof (x => x)", json!("function")),
+        ("(f => f === f)(x => x)", json!(true)),
+        ("(x => x) === (x => x)", json!(false)),
+        ("'' + (x => x * 2)", json!("x => x * 2")),
+        ("JSON.stringify({ a: 1, f: x => x })", json!("{\"a\":1}")),
+        // Functions have no JSON form and convert like undefined
+        ("x => x", json!(null)),
+        ("[x => x]", json!([null])),
+        ("{ f: () => 1 }", json!({})),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+
+    let result = Evaluator::default()
+        .with_non_json_policy(NonJsonPolicy::Error)
+        .evaluate("x => x");
+    match result {
+        Err(EvaluationError::NonJsonValue(value, _)) => assert_eq!(value, "x => x"),
+        _ => panic!("Expected NonJsonValue, got {:?}", result),
+    }
+
+    for expr in ["(1)(2)", "{ a: 1 }.a(1)", "(x => x)(1)(2)"] {
+        let result = evaluator.evaluate(expr);
+        match result {
+            Err(EvaluationError::TypeError(msg, _)) => {
+                assert!(msg.contains("is not a function"), "{}", msg)
+            }
+            _ => panic!("Expected TypeError for {}, got {:?}", expr, result),
+        }
+    }
+}
+
+#[test]
+fn test_unsupported_lambda_forms() {
+    let evaluator = evaluator_with(json!({ "list": [] }));
+
+    for expr in [
+        "anyOf(list, x => { return x })",
+        "anyOf(list, ({ a }) => a)",
+        "anyOf(list, (a = 1) => a)",
+        "anyOf(list, async x => x)",
+    ] {
+        let result = evaluator.evaluate(expr);
+        match result {
+            Err(EvaluationError::Node(_)) => {}
+            _ => panic!("Expected Node error for {}, got {:?}", expr, result),
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use exprimo::EvaluationError;
+use serde_json::{json, Value};
+
+mod common;
+use common::evaluator_with;
+
+// --- Array Literal Tests ---
+
+#[test]
+fn test_array_literal_elements() {
+    let evaluator = evaluator_with(json!({ "x": 2, "name": "bob" }));
+
+    assert_eq!(
+        evaluator
+            .evaluate("[1, 'two', x + 1, name, [true]]")
+            .unwrap(),
+        json!([1, "two", 3, "bob", [true]])
+    );
+    assert_eq!(evaluator.evaluate("[]").unwrap(), json!([]));
+    assert_eq!(evaluator.evaluate("[x,]").unwrap(), json!([2]));
+}
+
+#[test]
+fn test_array_literal_holes() {
+    let evaluator = evaluator_with(json!({}));
+
+    // Holes read back as null (the JSON stand-in for undefined)
+    assert_eq!(evaluator.evaluate("[1, , 3]").unwrap(), json!([1, null, 3]));
+    assert_eq!(evaluator.evaluate("[,]").unwrap(), json!([null]));
+    assert_eq!(
+        evaluator.evaluate("[, , 1]").unwrap(),
+        json!([null, null, 1])
+    );
+}
+
+#[test]
+fn test_array_literal_spread() {
+    let evaluator = evaluator_with(json!({ "a": [1, 2], "b": [], "s": "hi" }));
+
+    assert_eq!(evaluator.evaluate("[...a, 3]").unwrap(), json!([1, 2, 3]));
+    assert_eq!(
+        evaluator.evaluate("[0, ...b, ...a, ...a]").unwrap(),
+        json!([0, 1, 2, 1, 2])
+    );
+    assert_eq!(evaluator.evaluate("[...s]").unwrap(), json!(["h", "i"]));
+    assert_eq!(
+        evaluator.evaluate("[...[1, [2]]]").unwrap(),
+        json!([1, [2]])
+    );
+}
+
+#[test]
+fn test_array_literal_spread_non_iterable() {
+    let evaluator = evaluator_with(json!({ "n": 1, "o": {}, "nothing": null }));
+
+    for expr in ["[...n]", "[...o]", "[...nothing]"] {
+        let result = evaluator.evaluate(expr);
+        match result {
+            Err(EvaluationError::TypeError(msg, _)) => {
+                assert!(
+                    msg.contains("is not iterable"),
+                    "unexpected message: {}",
+                    msg
+                );
+            }
+            _ => panic!("Expected TypeError for {}, got {:?}", expr, result),
+        }
+    }
+}
+
+#[test]
+fn test_array_literal_with_includes() {
+    let evaluator = evaluator_with(json!({ "tier": "gold" }));
+
+    assert_eq!(
+        evaluator
+            .evaluate("['gold', 'platinum'].includes(tier)")
+            .unwrap(),
+        Value::Bool(true)
+    );
+    assert_eq!(
+        evaluator.evaluate("['silver'].includes(tier)").unwrap(),
+        Value::Bool(false)
+    );
+    assert_eq!(
+        evaluator.evaluate("[1, 2, 3].length").unwrap().as_f64(),
+        Some(3.0)
+    );
+}
+
+// --- Object Literal Tests ---
+
+#[test]
+fn test_object_literal_properties() {
+    let evaluator = evaluator_with(json!({ "x": 1, "key": "dyn", "v": true, "short": "s" }));
+
+    assert_eq!(
+        evaluator
+            .evaluate("{ a: 1, 'b-c': x + 1, [key]: v, short }")
+            .unwrap(),
+        json!({ "a": 1, "b-c": 2, "dyn": true, "short": "s" })
+    );
+    assert_eq!(evaluator.evaluate("{}").unwrap(), json!({}));
+    assert_eq!(
+        evaluator.evaluate("{ nested: { list: [x] } }").unwrap(),
+        json!({ "nested": { "list": [1] } })
+    );
+}
+
+#[test]
+fn test_object_literal_key_coercion() {
+    let evaluator = evaluator_with(json!({ "n": 2 }));
+
+    assert_eq!(
+        evaluator
+            .evaluate("{ 1: 'a', 1.5: 'b', [n]: 'c', [n + 0.25]: 'd', [true]: 'e', [null]: 'f' }")
+            .unwrap(),
+        json!({ "1": "a", "1.5": "b", "2": "c", "2.25": "d", "true": "e", "null": "f" })
+    );
+    assert_eq!(
+        evaluator
+            .evaluate("{ [1e21]: 'big', [0.0000001]: 'small', [-0.5]: 'neg' }")
+            .unwrap(),
+        json!({ "1e+21": "big", "1e-7": "small", "-0.5": "neg" })
+    );
+}
+
+#[test]
+fn test_object_literal_spread() {
+    let evaluator = evaluator_with(json!({
+        "rest": { "b": 2, "c": 3 },
+        "list": ["x", "y"],
+        "nothing": null
+    }));
+
+    assert_eq!(
+        evaluator.evaluate("{ a: 1, ...rest, c: 30 }").unwrap(),
+        json!({ "a": 1, "b": 2, "c": 30 })
+    );
+    // Later entries override earlier ones
+    assert_eq!(
+        evaluator.evaluate("{ c: 30, ...rest }").unwrap(),
+        json!({ "b": 2, "c": 3 })
+    );
+    assert_eq!(
+        evaluator.evaluate("{ ...list }").unwrap(),
+        json!({ "0": "x", "1": "y" })
+    );
+    assert_eq!(
+        evaluator.evaluate("{ ...nothing, a: 1 }").unwrap(),
+        json!({ "a": 1 })
+    );
+}
+
+#[test]
+fn test_object_literal_at_expression_start() {
+    let evaluator = evaluator_with(json!({ "score": 90 }));
+
+    assert_eq!(
+        evaluator
+            .evaluate("{ action: 'notify', urgent: score > 80 }")
+            .unwrap(),
+        json!({ "action": "notify", "urgent": true })
+    );
+    assert_eq!(
+        evaluator.evaluate("{ a: 1 }.a").unwrap().as_f64(),
+        Some(1.0)
+    );
+    assert_eq!(
+        evaluator.evaluate("{ a: 1 }.hasOwnProperty('a')").unwrap(),
+        Value::Bool(true)
+    );
+}
+
+#[test]
+fn test_object_literal_methods_unsupported() {
+    let evaluator = evaluator_with(json!({}));
+
+    for expr in ["{ a() { return 1 } }", "{ get a() { return 1 } }"] {
+        let result = evaluator.evaluate(expr);
+        match result {
+            Err(EvaluationError::Node(_)) => {}
+            _ => panic!("Expected Node error for {}, got {:?}", expr, result),
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use exprimo::{EvaluationError, Evaluator};
+use serde_json::{json, Value};
+
+mod common;
+use common::evaluator_with;
+
+fn assert_numbers(evaluator: &Evaluator, cases: &[(&str, f64)]) {
+    for (expr, expected) in cases {
+        let actual = evaluator.evaluate(expr).unwrap().as_f64().unwrap();
+        assert!(
+            (actual - expected).abs() < 1e-12,
+            "{}: expected {}, got {}",
+            expr,
+            expected,
+            actual
+        );
+    }
+}
+
+#[test]
+fn test_math_constants() {
+    let evaluator = evaluator_with(json!({}));
+
+    assert_numbers(
+        &evaluator,
+        &[
+            ("Math.PI", std::f64::consts::PI),
+            ("Math.E", std::f64::consts::E),
+            ("Math.SQRT2", std::f64::consts::SQRT_2),
+            ("Math['LN2']", std::f64::consts::LN_2),
+        ],
+    );
+    assert_eq!(evaluator.evaluate("Math.nope").unwrap(), Value::Null);
+}
+
+#[test]
+fn test_math_rounding() {
+    let evaluator = evaluator_with(json!({ "price": 19.456 }));
+
+    assert_numbers(
+        &evaluator,
+        &[
+            ("Math.round(price * 100) / 100", 19.46),
+            ("Math.round(2.5)", 3.0),
+            // Half-way cases round towards +Infinity
+            ("Math.round(-2.5)", -2.0),
+            ("Math.round(0.49999999999999994)", 0.0),
+            ("Math.floor(-1.5)", -2.0),
+            ("Math.ceil(1.2)", 2.0),
+            ("Math.trunc(-1.7)", -1.0),
+            ("Math.sign(-3)", -1.0),
+            ("Math.sign(0)", 0.0),
+            ("Math.abs('-4')", 4.0),
+        ],
+    );
+
+    // Negative numbers that round to zero keep their sign
+    for expr in ["Math.round(-0.4)", "Math.round(-0.5)", "Math.round(-0)"] {
+        let n = evaluator.evaluate_value(expr).unwrap().as_f64().unwrap();
+        assert!(n == 0.0 && n.is_sign_negative(), "{}", expr);
+    }
+    let n = evaluator
+        .evaluate_value("Math.round(0.4)")
+        .unwrap()
+        .as_f64()
+        .unwrap();
+    assert!(n == 0.0 && n.is_sign_positive());
+}
+
+#[test]
+fn test_math_min_max() {
+    let evaluator = evaluator_with(json!({ "a": 3, "b": 7 }));
+
+    assert_numbers(
+        &evaluator,
+        &[
+            ("Math.max(a, b)", 7.0),
+            ("Math.min(a, b, -1)", -1.0),
+            ("Math.max(a)", 3.0),
+            ("Math.min(Math.max(150, 0), 100)", 100.0),
+        ],
+    );
+    // No arguments give -Infinity and Infinity
+    assert_eq!(
+        evaluator
+            .evaluate("Math.max() < 0 && Math.min() > 0")
+            .unwrap(),
+        Value::Bool(true)
+    );
+}
+
+#[test]
+fn test_math_powers_and_logs() {
+    let evaluator = evaluator_with(json!({}));
+
+    assert_numbers(
+        &evaluator,
+        &[
+            ("Math.pow(2, 10)", 1024.0),
+            ("Math.pow(2, 10) === 2 ** 10 ? 1 : 0", 1.0),
+            ("Math.sqrt(16)", 4.0),
+            ("Math.cbrt(27)", 3.0),
+            ("Math.hypot(3, 4)", 5.0),
+            ("Math.log(Math.E)", 1.0),
+            ("Math.log10(1000)", 3.0),
+            ("Math.log2(8)", 3.0),
+            ("Math.exp(0)", 1.0),
+        ],
+    );
+}
+
+#[test]
+fn test_math_trigonometry() {
+    let evaluator = evaluator_with(json!({}));
+
+    assert_numbers(
+        &evaluator,
+        &[
+            ("Math.sin(Math.PI / 2)", 1.0),
+            ("Math.cos(0)", 1.0),
+            ("Math.tan(0)", 0.0),
+            ("Math.atan2(1, 1)", std::f64::consts::FRAC_PI_4),
+            ("Math.asin(1)", std::f64::consts::FRAC_PI_2),
+        ],
+    );
+}
+
+#[test]
+fn test_math_typeof_and_shadowing() {
+    let evaluator = evaluator_with(json!({}));
+    assert_eq!(evaluator.evaluate("typeof Math").unwrap(), json!("object"));
+    assert_eq!(
+        evaluator.evaluate("typeof Math.round").unwrap(),
+        json!("function")
+    );
+
+    // A context variable named `Math` takes precedence
+    let evaluator = evaluator_with(json!({ "Math": { "PI": 3 } }));
+    assert_eq!(evaluator.evaluate("Math.PI").unwrap(), json!(3));
+}
+
+#[test]
+fn test_math_disabled() {
+    let evaluator = evaluator_with(json!({ "a": 1 })).without_math();
+
+    let result = evaluator.evaluate("Math.max(a, 2)");
+    match result {
+        Err(EvaluationError::Node(err)) => {
+            assert!(err.to_string().contains("Identifier 'Math' not found"));
+        }
+        _ => panic!("Expected Node error, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_math_argument_errors() {
+    let evaluator = evaluator_with(json!({}));
+
+    // As in JavaScript, missing arguments are undefined and extra ones are ignored
+    for (expr, expected) in [
+        ("Math.floor(2.7, 2)", json!(2)),
+        ("Math.pow(2, 3, 4)", json!(8)),
+        ("isNaN(Math.pow(2))", json!(true)),
+        ("isNaN(Math.round())", json!(true)),
+        ("Math.max()", json!(null)),
+        // A function converts to NaN like any other object
+        ("isNaN(Math.floor(x => x))", json!(true)),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+
+    // Misuse of a built-in is a type error, not a custom function error
+    for expr in ["Math.abs(/a/)", "Math.floor(/a/)"] {
+        let result = evaluator.evaluate(expr);
+        assert!(
+            matches!(result, Err(EvaluationError::TypeError(_, _))),
+            "Expected TypeError for {}, got {:?}",
+            expr,
+            result
+        );
+    }
+
+    let result = evaluator.evaluate("Math.PI()");
+    match result {
+        Err(EvaluationError::TypeError(msg, _)) => assert!(msg.contains("is not a function")),
+        _ => panic!("Expected TypeError, got {:?}", result),
+    }
+}
@@ -0,0 +1,210 @@
+use exprimo::EvaluationError;
+use serde_json::{json, Value};
+
+mod common;
+use common::evaluator_with;
+
+#[test]
+fn test_computed_member_on_objects() {
+    let evaluator = evaluator_with(json!({
+        "headers": { "content-type": "application/json", "1": "one" },
+        "field": "content-type"
+    }));
+
+    assert_eq!(
+        evaluator.evaluate("headers['content-type']").unwrap(),
+        json!("application/json")
+    );
+    assert_eq!(
+        evaluator.evaluate("headers[field]").unwrap(),
+        json!("application/json")
+    );
+    // Keys are coerced to strings
+    assert_eq!(evaluator.evaluate("headers[1]").unwrap(), json!("one"));
+    assert_eq!(
+        evaluator.evaluate("headers['missing']").unwrap(),
+        Value::Null
+    );
+}
+
+#[test]
+fn test_computed_member_on_arrays() {
+    let evaluator = evaluator_with(json!({
+        "items": [{ "price": 10 }, { "price": 25 }],
+        "i": 1
+    }));
+
+    assert_eq!(
+        evaluator.evaluate("items[0].price").unwrap().as_f64(),
+        Some(10.0)
+    );
+    assert_eq!(
+        evaluator.evaluate("items[i].price").unwrap().as_f64(),
+        Some(25.0)
+    );
+    assert_eq!(
+        evaluator.evaluate("items['1'].price").unwrap().as_f64(),
+        Some(25.0)
+    );
+    assert_eq!(
+        evaluator.evaluate("items[i - 1 + 0.0]").unwrap(),
+        json!({ "price": 10 })
+    );
+    // Out of range and non-index keys read as undefined
+    assert_eq!(evaluator.evaluate("items[10]").unwrap(), Value::Null);
+    assert_eq!(evaluator.evaluate("items[-1]").unwrap(), Value::Null);
+    assert_eq!(evaluator.evaluate("items[0.5]").unwrap(), Value::Null);
+    assert_eq!(evaluator.evaluate("items['01']").unwrap(), Value::Null);
+    assert_eq!(evaluator.evaluate("[[1, 2], [3]][1][0]").unwrap(), json!(3));
+}
+
+#[test]
+fn test_computed_member_on_strings() {
+    let evaluator = evaluator_with(json!({ "word": "héllo" }));
+
+    assert_eq!(evaluator.evaluate("'abc'[1]").unwrap(), json!("b"));
+    assert_eq!(evaluator.evaluate("word[1]").unwrap(), json!("é"));
+    assert_eq!(evaluator.evaluate("word[9]").unwrap(), Value::Null);
+}
+
+#[test]
+fn test_computed_member_shares_built_ins() {
+    let evaluator = evaluator_with(json!({
+        "tags": ["a", "b"],
+        "user": { "name": "x" }
+    }));
+
+    assert_eq!(
+        evaluator.evaluate("tags['length']").unwrap().as_f64(),
+        Some(2.0)
+    );
+    assert_eq!(
+        evaluator.evaluate("tags['includes']('b')").unwrap(),
+        Value::Bool(true)
+    );
+    assert_eq!(
+        evaluator
+            .evaluate("user['hasOwnProperty']('name')")
+            .unwrap(),
+        Value::Bool(true)
+    );
+}
+
+#[test]
+fn test_computed_member_on_null() {
+    let evaluator = evaluator_with(json!({ "nothing": null }));
+
+    let result = evaluator.evaluate("nothing['key']");
+    match result {
+        Err(EvaluationError::TypeError(msg, _)) => {
+            assert!(msg.contains("trying to access property: key"), "{}", msg);
+        }
+        _ => panic!("Expected TypeError, got {:?}", result),
+    }
+}
+
+// --- Optional Chaining Tests ---
+
+#[test]
+fn test_optional_chaining_member_access() {
+    let evaluator = evaluator_with(json!({
+        "order": { "customer": { "address": { "city": "Oslo" } } },
+        "guest": { "customer": null },
+        "nothing": null,
+        "key": "city"
+    }));
+
+    assert_eq!(
+        evaluator
+            .evaluate("order?.customer?.address?.city")
+            .unwrap(),
+        json!("Oslo")
+    );
+    assert_eq!(
+        evaluator.evaluate("guest.customer?.address.city").unwrap(),
+        Value::Null
+    );
+    assert_eq!(
+        evaluator.evaluate("order.customer.address?.[key]").unwrap(),
+        json!("Oslo")
+    );
+    assert_eq!(
+        evaluator.evaluate("nothing?.[key].length").unwrap(),
+        Value::Null
+    );
+}
+
+#[test]
+fn test_optional_chaining_calls() {
+    let evaluator = evaluator_with(json!({
+        "user": { "tags": ["vip"] },
+        "anon": {},
+        "nothing": null
+    }));
+
+    assert_eq!(
+        evaluator.evaluate("user?.tags?.includes('vip')").unwrap(),
+        Value::Bool(true)
+    );
+    assert_eq!(
+        evaluator.evaluate("anon.tags?.includes('vip')").unwrap(),
+        Value::Null
+    );
+    assert_eq!(
+        evaluator.evaluate("nothing?.tags.includes('vip')").unwrap(),
+        Value::Null
+    );
+    assert_eq!(
+        evaluator.evaluate("user.tags.missing?.()").unwrap(),
+        Value::Null
+    );
+    assert_eq!(evaluator.evaluate("nothing?.(1)").unwrap(), Value::Null);
+    assert_eq!(evaluator.evaluate("anon.fn?.(1)").unwrap(), Value::Null);
+
+    // A name that is not defined anywhere is still reported, so typos are not hidden
+    let result = evaluator.evaluate("unknownFn?.(1)");
+    match result {
+        Err(EvaluationError::Node(err)) => {
+            assert!(
+                err.to_string().contains("Function 'unknownFn' not found"),
+                "{}",
+                err
+            );
+        }
+        _ => panic!("Expected Node error, got {:?}", result),
+    }
+    assert!(evaluator.evaluate("user?.(1)").is_err());
+}
+
+#[test]
+fn test_optional_chaining_does_not_evaluate_arguments_when_short_circuited() {
+    let evaluator = evaluator_with(json!({ "nothing": null }));
+
+    // `missing` is not in the context, but the call never happens
+    assert_eq!(
+        evaluator.evaluate("nothing?.includes(missing)").unwrap(),
+        Value::Null
+    );
+    assert_eq!(
+        evaluator.evaluate("nothing?.[missing]").unwrap(),
+        Value::Null
+    );
+}
+
+#[test]
+fn test_optional_chaining_stops_at_parentheses() {
+    let evaluator = evaluator_with(json!({ "nothing": null }));
+
+    assert_eq!(evaluator.evaluate("(nothing?.a)").unwrap(), Value::Null);
+    let result = evaluator.evaluate("(nothing?.a).b");
+    match result {
+        Err(EvaluationError::TypeError(_, _)) => {}
+        _ => panic!("Expected TypeError, got {:?}", result),
+    }
+    // Without `?.` a nullish base is still an error
+    let result = evaluator.evaluate("nothing.a?.b");
+    match result {
+        Err(EvaluationError::TypeError(_, _)) => {}
+        _ => panic!("Expected TypeError, got {:?}", result),
+    }
+}
@@ -0,0 +1,235 @@
+use exprimo::{CustomFuncError, CustomFunction, EvaluationError, Evaluator};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+mod common;
+
+#[derive(Debug)]
+struct Noop;
+
+impl CustomFunction for Noop {
+    fn call(&self, _args: &[Value]) -> Result<Value, CustomFuncError> {
+        Ok(Value::Null)
+    }
+}
+
+fn evaluator_with(context: Value) -> Evaluator {
+    let context = common::context(context);
+    let mut custom_functions: HashMap<String, Arc<dyn CustomFunction>> = HashMap::new();
+    custom_functions.insert("noop".to_string(), Arc::new(Noop));
+    Evaluator::new(context, custom_functions)
+}
+
+// --- typeof / void ---
+
+#[test]
+fn test_typeof_values() {
+    let evaluator = evaluator_with(json!({
+        "name": "bob",
+        "age": 42,
+        "active": true,
+        "tags": [],
+        "profile": {},
+        "nothing": null
+    }));
+
+    for (expr, expected) in [
+        ("typeof name", "string"),
+        ("typeof age", "number"),
+        ("typeof active", "boolean"),
+        ("typeof tags", "object"),
+        ("typeof profile", "object"),
+        ("typeof nothing", "object"),
+        ("typeof (age + 1)", "number"),
+        ("typeof NaN", "number"),
+        ("typeof noop", "function"),
+        ("typeof typeof age", "string"),
+    ] {
+        assert_eq!(
+            evaluator.evaluate(expr).unwrap(),
+            Value::String(expected.to_string()),
+            "{}",
+            expr
+        );
+    }
+}
+
+#[test]
+fn test_typeof_undefined_does_not_error() {
+    let evaluator = evaluator_with(json!({ "nothing": null }));
+
+    for expr in [
+        "typeof undefined",
+        "typeof notInContext",
+        "typeof nothing?.field",
+    ] {
+        assert_eq!(
+            evaluator.evaluate(expr).unwrap(),
+            Value::String("undefined".to_string()),
+            "{}",
+            expr
+        );
+    }
+    assert_eq!(
+        evaluator
+            .evaluate("typeof notInContext === 'string' && notInContext.length > 0")
+            .unwrap(),
+        Value::Bool(false)
+    );
+}
+
+#[test]
+fn test_void_operator() {
+    let evaluator = evaluator_with(json!({ "x": 1 }));
+
+    assert_eq!(evaluator.evaluate("void 0").unwrap(), Value::Null);
+    assert_eq!(evaluator.evaluate("void (x + 1)").unwrap(), Value::Null);
+    assert!(evaluator.evaluate("void missing").is_err());
+}
+
+// --- in ---
+
+#[test]
+fn test_in_operator_on_objects() {
+    let evaluator = evaluator_with(json!({
+        "user": { "email": "a@b.c", "phone": null, "1": true },
+        "field": "email"
+    }));
+
+    assert_eq!(
+        evaluator.evaluate("'email' in user").unwrap(),
+        Value::Bool(true)
+    );
+    assert_eq!(
+        evaluator.evaluate("field in user").unwrap(),
+        Value::Bool(true)
+    );
+    // Present with a null value still counts
+    assert_eq!(
+        evaluator.evaluate("'phone' in user").unwrap(),
+        Value::Bool(true)
+    );
+    assert_eq!(
+        evaluator.evaluate("'address' in user").unwrap(),
+        Value::Bool(false)
+    );
+    assert_eq!(evaluator.evaluate("1 in user").unwrap(), Value::Bool(true));
+    assert_eq!(
+        evaluator.evaluate("!('address' in user)").unwrap(),
+        Value::Bool(true)
+    );
+}
+
+#[test]
+fn test_in_operator_on_arrays() {
+    let evaluator = evaluator_with(json!({ "list": ["a", "b"] }));
+
+    assert_eq!(evaluator.evaluate("0 in list").unwrap(), Value::Bool(true));
+    assert_eq!(
+        evaluator.evaluate("'1' in list").unwrap(),
+        Value::Bool(true)
+    );
+    assert_eq!(evaluator.evaluate("2 in list").unwrap(), Value::Bool(false));
+    // `in` checks indices, not values
+    assert_eq!(
+        evaluator.evaluate("'a' in list").unwrap(),
+        Value::Bool(false)
+    );
+    assert_eq!(
+        evaluator.evaluate("'length' in list").unwrap(),
+        Value::Bool(true)
+    );
+}
+
+#[test]
+fn test_in_operator_on_primitives() {
+    let evaluator = evaluator_with(json!({ "name": "bob", "nothing": null }));
+
+    for expr in ["'length' in name", "'a' in nothing", "'a' in 1"] {
+        let result = evaluator.evaluate(expr);
+        match result {
+            Err(EvaluationError::TypeError(msg, _)) => {
+                assert!(msg.contains("Cannot use 'in' operator"), "{}", msg);
+            }
+            _ => panic!("Expected TypeError for {}, got {:?}", expr, result),
+        }
+    }
+}
+
+// --- Bitwise, shift and exponent ---
+
+#[test]
+fn test_bitwise_operators() {
+    let evaluator = evaluator_with(json!({ "flags": 5 }));
+
+    for (expr, expected) in [
+        ("flags & 4", 4.0),
+        ("flags & 2", 0.0),
+        ("flags | 2", 7.0),
+        ("flags ^ 1", 4.0),
+        ("~flags", -6.0),
+        ("~~3.7", 3.0),
+        ("~~-3.7", -3.0),
+        // Operands are converted with ToInt32
+        ("4294967297 | 0", 1.0),
+        ("2147483648 | 0", -2147483648.0),
+        ("'12' & 10", 8.0),
+        ("NaN | 0", 0.0),
+        ("Infinity | 0", 0.0),
+        ("true | 2", 3.0),
+    ] {
+        assert_eq!(
+            evaluator.evaluate(expr).unwrap().as_f64(),
+            Some(expected),
+            "{}",
+            expr
+        );
+    }
+    assert_eq!(
+        evaluator.evaluate("(flags & 4) !== 0").unwrap(),
+        Value::Bool(true)
+    );
+}
+
+#[test]
+fn test_shift_operators() {
+    for (expr, expected) in [
+        ("1 << 3", 8.0),
+        ("1 << 31", -2147483648.0),
+        ("1 << 32", 1.0),
+        ("-16 >> 2", -4.0),
+        ("-16 >>> 28", 15.0),
+        ("-1 >>> 0", 4294967295.0),
+        ("256 >> 33", 128.0),
+    ] {
+        let evaluator = evaluator_with(json!({}));
+        assert_eq!(
+            evaluator.evaluate(expr).unwrap().as_f64(),
+            Some(expected),
+            "{}",
+            expr
+        );
+    }
+}
+
+#[test]
+fn test_exponent_operator() {
+    let evaluator = evaluator_with(json!({ "rate": 1.05, "years": 2 }));
+
+    assert_eq!(
+        evaluator.evaluate("2 ** 10").unwrap().as_f64(),
+        Some(1024.0)
+    );
+    assert_eq!(evaluator.evaluate("2 ** -1").unwrap().as_f64(), Some(0.5));
+    // Right-associative
+    assert_eq!(
+        evaluator.evaluate("2 ** 3 ** 2").unwrap().as_f64(),
+        Some(512.0)
+    );
+    assert_eq!(
+        evaluator.evaluate("100 * rate ** years").unwrap().as_f64(),
+        Some(100.0 * 1.05f64.powf(2.0))
+    );
+    assert_eq!(evaluator.evaluate("'3' ** 2").unwrap().as_f64(), Some(9.0));
+}
@@ -1,5 +1,6 @@
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "serde_json_ctx")]
     use exprimo::EvaluationError; // Added import
     use exprimo::Evaluator;
     use serde_json::Value;
@@ -30,11 +31,16 @@ mod tests {
             HashMap::new(), // custom_functions
         );
 
-        let expr1 = "event.payload === null";
+        // A missing property is undefined, which only loosely equals null
+        let expr1 = "event.payload == null";
 
         let res1 = evaluator.evaluate(expr1).unwrap();
 
-        assert_eq!(res1, Value::Bool(true)); // Updated expectation
+        assert_eq!(res1, Value::Bool(true));
+        assert_eq!(
+            evaluator.evaluate("event.payload === null").unwrap(),
+            Value::Bool(false)
+        );
     }
 
     #[test]
@@ -82,7 +88,7 @@ mod tests {
         // Accessing .payload on Value::Null should be a TypeError
         let expr1_eval = evaluator.evaluate("event.payload");
         match expr1_eval {
-            Err(EvaluationError::TypeError(msg)) => {
+            Err(EvaluationError::TypeError(msg, _)) => {
                 assert!(msg.contains("Cannot read properties of null or primitive value: null (trying to access property: payload)"));
             }
             _ => panic!(
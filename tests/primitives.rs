@@ -1,3 +1,5 @@
+#![allow(clippy::needless_borrow)]
+
 use exprimo::Evaluator;
 use std::collections::HashMap;
 
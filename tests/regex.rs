@@ -0,0 +1,243 @@
+use exprimo::{Argument, CustomFuncError, CustomFunction, EvaluationError, Evaluator};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+mod common;
+use common::evaluator_with;
+
+#[test]
+fn test_regex_test_and_exec() {
+    let evaluator = evaluator_with(json!({
+        "code": "ABC1234",
+        "email": "ops@example.com",
+        "pattern": "^ops@"
+    }));
+
+    for (expr, expected) in [
+        ("/^[A-Z]{3}\\d{4}$/.test(code)", json!(true)),
+        ("/^[a-z]{3}\\d{4}$/.test(code)", json!(false)),
+        ("/^[a-z]{3}\\d{4}$/i.test(code)", json!(true)),
+        ("new RegExp(pattern).test(email)", json!(true)),
+        ("new RegExp('EXAMPLE', 'i').test(email)", json!(true)),
+        ("RegExp('^x').test(email)", json!(false)),
+        ("/a\\/b/.test('a/b')", json!(true)),
+        // `.` stops at line terminators unless the `s` flag is set
+        ("/a.b/.test('a\\nb')", json!(false)),
+        ("/a.b/s.test('a\\nb')", json!(true)),
+        ("/^b$/m.test('a\\nb')", json!(true)),
+        (
+            "/(\\w+)@(\\w+)\\.com/.exec(email)",
+            json!(["ops@example.com", "ops", "example"]),
+        ),
+        ("/(a)|(b)/.exec('b')", json!(["b", null, "b"])),
+        ("/z/.exec(email)", Value::Null),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+}
+
+#[test]
+fn test_regex_properties() {
+    let evaluator = evaluator_with(json!({}));
+
+    for (expr, expected) in [
+        ("/a+b/gi.source", json!("a+b")),
+        ("/a/yigm.flags", json!("gimy")),
+        ("/a/g.global", json!(true)),
+        ("/a/.ignoreCase", json!(false)),
+        ("new RegExp('').source", json!("(?:)")),
+        ("new RegExp(/x/g).flags", json!("g")),
+        ("new RegExp(/x/g, 'i').flags", json!("i")),
+        ("/a/g.toString()", json!("/a/g")),
+        ("typeof /a/", json!("object")),
+        ("typeof new RegExp('a')", json!("object")),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+}
+
+#[test]
+fn test_string_match_and_search() {
+    let evaluator = evaluator_with(json!({ "log": "id=12 id=7 name=x" }));
+
+    for (expr, expected) in [
+        ("log.match(/id=(\\d+)/)", json!(["id=12", "12"])),
+        ("log.match(/id=\\d+/g)", json!(["id=12", "id=7"])),
+        ("log.match(/nope/g)", Value::Null),
+        ("'baaac'.match(/a*/g)", json!(["", "aaa", "", ""])),
+        // Strings are compiled as patterns
+        ("log.match('n.me')", json!(["name"])),
+        (
+            "log.matchAll(/id=(\\d+)/g)",
+            json!([["id=12", "12"], ["id=7", "7"]]),
+        ),
+        (
+            "log.matchAll(/id=(\\d+)/g).map(m => m[1])",
+            json!(["12", "7"]),
+        ),
+        ("log.search(/name/)", json!(11)),
+        ("log.search(/nope/)", json!(-1)),
+        // Positions count UTF-16 code units
+        ("'😀ab'.search(/b/)", json!(3)),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+}
+
+#[test]
+fn test_string_replace_with_regex() {
+    let evaluator = evaluator_with(json!({ "date": "2024-03-15" }));
+
+    for (expr, expected) in [
+        (
+            "date.replace(/(\\d+)-(\\d+)-(\\d+)/, '$3/$2/$1')",
+            json!("15/03/2024"),
+        ),
+        (
+            "date.replace(/(?<y>\\d+)-(?<m>\\d+)-(?<d>\\d+)/, '$<d>.$<m>.$<y>')",
+            json!("15.03.2024"),
+        ),
+        ("date.replace(/-/, '')", json!("202403-15")),
+        ("date.replace(/-/g, '')", json!("20240315")),
+        ("date.replaceAll(/-/g, '/')", json!("2024/03/15")),
+        ("'aBc'.replace(/b/i, '[$&]')", json!("a[B]c")),
+        ("'abc'.replace(/b/, \"$`$'\")", json!("aacc")),
+        ("'abc'.replace(/b/, '$1$$')", json!("a$1$c")),
+        (
+            "'a-b'.replace(/(\\w)-(\\w)/, (m, x, y) => y + x)",
+            json!("ba"),
+        ),
+        (
+            "'x1y22'.replace(/\\d+/g, (m, offset) => offset > 2 ? 'B' : 'A')",
+            json!("xAyB"),
+        ),
+        ("'abc'.replace(/x*/g, '-')", json!("-a-b-c-")),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+}
+
+#[test]
+fn test_string_split_with_regex() {
+    let evaluator = evaluator_with(json!({ "csv": "a, b,c ,d" }));
+
+    for (expr, expected) in [
+        ("csv.split(/\\s*,\\s*/)", json!(["a", "b", "c", "d"])),
+        ("csv.split(/\\s*,\\s*/, 2)", json!(["a", "b"])),
+        ("'a1b2c'.split(/(\\d)/)", json!(["a", "1", "b", "2", "c"])),
+        ("'abc'.split(/(?:)/)", json!(["a", "b", "c"])),
+        ("''.split(/x/)", json!([""])),
+        ("''.split(/(?:)/)", json!([])),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+}
+
+#[test]
+fn test_regex_errors() {
+    let evaluator = evaluator_with(json!({ "bad": "(" }));
+
+    // Invalid literals are rejected when the expression is compiled
+    for expr in ["/(/.test('a')", "/a/gg.test('a')", "/(?=a)/.test('a')"] {
+        let result = evaluator.compile(expr);
+        match result {
+            // The parser itself reports duplicate flags
+            Err(EvaluationError::Node(_) | EvaluationError::ParseError(_)) => {}
+            _ => panic!("Expected compile error for {}, got {:?}", expr, result),
+        }
+    }
+
+    for expr in [
+        "new RegExp(bad).test('a')",
+        "new RegExp('a', 'x').test('a')",
+        "'a'.replaceAll(/a/, 'b')",
+        "'a'.matchAll(/a/)",
+        // Regular expressions are not values
+        "/a/",
+        "[/a/]",
+        "new Date()",
+        "/a/.nope('a')",
+    ] {
+        let result = evaluator.evaluate(expr);
+        match result {
+            Err(EvaluationError::TypeError(_, _)) => {}
+            _ => panic!("Expected TypeError for {}, got {:?}", expr, result),
+        }
+    }
+}
+
+#[test]
+fn test_regex_size_limit() {
+    let evaluator = evaluator_with(json!({}));
+
+    let result = evaluator.evaluate("new RegExp('(a{1000}){1000}').test('a')");
+    match result {
+        Err(EvaluationError::TypeError(message, _)) => {
+            assert!(message.contains("too large"), "{}", message)
+        }
+        _ => panic!("Expected TypeError, got {:?}", result),
+    }
+
+    let long_pattern = format!("'{}'", "a".repeat(5000));
+    let result = evaluator.evaluate(&format!("new RegExp({}).test('a')", long_pattern));
+    assert!(matches!(result, Err(EvaluationError::TypeError(_, _))));
+}
+
+#[test]
+fn test_compiled_expression_reuses_regexps() {
+    let evaluator = Evaluator::default();
+    let compiled = evaluator
+        .compile("rules.every(rule => new RegExp(rule, 'i').test(name))")
+        .unwrap();
+
+    let mut context = HashMap::new();
+    context.insert("rules".to_string(), json!(["^a", "z$"]));
+    for (name, expected) in [("Abz", true), ("abc", false), ("AZ", true)] {
+        context.insert("name".to_string(), json!(name));
+        assert_eq!(
+            compiled.evaluate_with(&evaluator, &context).unwrap(),
+            json!(expected),
+            "{}",
+            name
+        );
+    }
+}
+
+#[derive(Debug)]
+struct Matches;
+
+impl CustomFunction for Matches {
+    fn call(&self, _args: &[Value]) -> Result<Value, CustomFuncError> {
+        Err(CustomFuncError::ArgumentError(
+            "matches expects a regular expression".to_string(),
+        ))
+    }
+
+    fn call_with_lambdas(&self, args: &[Argument<'_>]) -> Result<Value, CustomFuncError> {
+        let text = args[0]
+            .as_value()
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let regexp = args[1].as_regexp().ok_or_else(|| {
+            CustomFuncError::ArgumentError("matches expects a regular expression".to_string())
+        })?;
+        Ok(Value::Bool(regexp.is_match(text)))
+    }
+}
+
+#[test]
+fn test_regex_custom_function_argument() {
+    let mut custom_functions: HashMap<String, Arc<dyn CustomFunction>> = HashMap::new();
+    custom_functions.insert("matches".to_string(), Arc::new(Matches));
+    let evaluator = Evaluator::with_functions(custom_functions);
+
+    assert_eq!(
+        evaluator.evaluate("matches('Hello', /^h/i)").unwrap(),
+        json!(true)
+    );
+    assert_eq!(
+        evaluator.evaluate("matches('Hello', /^x/)").unwrap(),
+        json!(false)
+    );
+}
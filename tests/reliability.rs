@@ -1,4 +1,4 @@
-use exprimo::Evaluator;
+use exprimo::{EvaluationError, Evaluator};
 use serde_json::Value;
 use std::collections::HashMap;
 
@@ -50,14 +50,10 @@ fn test_nan_comparison() {
     let result = evaluator.evaluate("NaN == NaN");
     // The NaN identifier may not work perfectly due to serde_json::Number limitations
     // but the comparison logic is correct
-    if result.is_ok() {
-        // If it works, it should be false
-        let val = result.unwrap();
-        if let Value::Bool(b) = val {
-            // NaN == NaN should be false in JavaScript
-            // But due to our workaround, it might not work perfectly
-            // We'll just verify the test doesn't panic
-        }
+    if let Ok(Value::Bool(_b)) = result {
+        // NaN == NaN should be false in JavaScript
+        // But due to our workaround, it might not work perfectly
+        // We'll just verify the test doesn't panic
     }
 
     // Test NaN from actual operations (more reliable than NaN identifier)
@@ -120,11 +116,13 @@ fn test_empty_object_truthiness() {
     let result = evaluator.evaluate("emptyObj ? true : false").unwrap();
     assert_eq!(result, Value::Bool(true), "Empty object should be truthy");
 
-    // Note: {} in expression context is parsed as a block statement, not an object literal
-    // This is a JavaScript quirk. To test object literal, we need to use it in a different context
-    // For now, we'll skip the literal test and just test the variable
-    // let result = evaluator.evaluate("{} ? true : false").unwrap();
-    // assert_eq!(result, Value::Bool(true), "Empty object literal should be truthy");
+    // Expressions are parsed in expression position, so a leading {} is an object literal
+    let result = evaluator.evaluate("{} ? true : false").unwrap();
+    assert_eq!(
+        result,
+        Value::Bool(true),
+        "Empty object literal should be truthy"
+    );
 }
 
 #[test]
@@ -398,7 +396,11 @@ fn test_logical_operators_with_new_truthiness() {
 
     // Empty object in logical OR
     let result = evaluator.evaluate("emptyObj || false").unwrap();
-    assert_eq!(result, Value::Bool(true), "Empty object is truthy");
+    assert_eq!(
+        result,
+        Value::Object(serde_json::Map::new()),
+        "Empty object is truthy, so OR should return it"
+    );
 
     // Negation of empty array
     let result = evaluator.evaluate("!emptyArr").unwrap();
@@ -408,3 +410,125 @@ fn test_logical_operators_with_new_truthiness() {
         "Negation of truthy empty array should be false"
     );
 }
+
+#[test]
+fn test_logical_operators_short_circuit() {
+    let mut context = HashMap::new();
+    context.insert("user".to_string(), Value::Null);
+    context.insert("name".to_string(), Value::String("".to_string()));
+
+    let evaluator = Evaluator::new(context, HashMap::new());
+
+    // The right-hand side would throw if it were evaluated
+    let result = evaluator.evaluate("user && user.name").unwrap();
+    assert_eq!(
+        result,
+        Value::Null,
+        "AND should stop at a falsy left operand"
+    );
+
+    let result = evaluator.evaluate("true || missing_identifier").unwrap();
+    assert_eq!(
+        result,
+        Value::Bool(true),
+        "OR should stop at a truthy left operand"
+    );
+
+    let result = evaluator.evaluate("false && missing_identifier").unwrap();
+    assert_eq!(result, Value::Bool(false));
+
+    // When the left operand does not decide, the right one is evaluated
+    assert!(evaluator.evaluate("true && missing_identifier").is_err());
+}
+
+#[test]
+fn test_logical_operators_return_operands() {
+    let mut context = HashMap::new();
+    context.insert("name".to_string(), Value::String("".to_string()));
+    context.insert("nickname".to_string(), Value::String("Bob".to_string()));
+
+    let evaluator = Evaluator::new(context, HashMap::new());
+
+    let result = evaluator.evaluate("name || 'anonymous'").unwrap();
+    assert_eq!(result, Value::String("anonymous".to_string()));
+
+    let result = evaluator.evaluate("nickname || 'anonymous'").unwrap();
+    assert_eq!(result, Value::String("Bob".to_string()));
+
+    let result = evaluator.evaluate("nickname && 'has nickname'").unwrap();
+    assert_eq!(result, Value::String("has nickname".to_string()));
+
+    let result = evaluator.evaluate("name && 'has name'").unwrap();
+    assert_eq!(result, Value::String("".to_string()));
+
+    let result = evaluator.evaluate("0 || null || 'fallback'").unwrap();
+    assert_eq!(result, Value::String("fallback".to_string()));
+}
+
+#[test]
+fn test_nullish_coalescing() {
+    let mut context = HashMap::new();
+    context.insert("discount".to_string(), Value::from(0));
+    context.insert("label".to_string(), Value::String("".to_string()));
+    context.insert("missing".to_string(), Value::Null);
+
+    let evaluator = Evaluator::new(context, HashMap::new());
+
+    // Unlike `||`, only null falls through to the default
+    let result = evaluator.evaluate("discount ?? 10").unwrap();
+    assert_eq!(result.as_f64(), Some(0.0));
+
+    let result = evaluator.evaluate("label ?? 'none'").unwrap();
+    assert_eq!(result, Value::String("".to_string()));
+
+    let result = evaluator.evaluate("missing ?? 'none'").unwrap();
+    assert_eq!(result, Value::String("none".to_string()));
+
+    let result = evaluator.evaluate("missing ?? null ?? false").unwrap();
+    assert_eq!(result, Value::Bool(false));
+
+    // The right-hand side is not evaluated when the left is not null
+    let result = evaluator.evaluate("discount ?? undefinedVar").unwrap();
+    assert_eq!(result.as_f64(), Some(0.0));
+
+    let result = evaluator.evaluate("(missing || label) ?? 'none'").unwrap();
+    assert_eq!(result, Value::String("".to_string()));
+
+    let result = evaluator
+        .evaluate("missing ?? (discount && 'yes')")
+        .unwrap();
+    assert_eq!(result.as_f64(), Some(0.0));
+}
+
+#[test]
+fn test_nullish_coalescing_cannot_mix_with_logical_operators() {
+    let evaluator = Evaluator::new(HashMap::new(), HashMap::new());
+
+    for (expr, location) in [
+        ("a ?? b || c", (7, 9)),
+        ("a || b ?? c", (7, 9)),
+        ("a && b ?? c", (7, 9)),
+        ("a ?? b && c", (2, 4)),
+    ] {
+        match evaluator.evaluate(expr) {
+            Err(EvaluationError::Node(err)) => {
+                let message = err.to_string();
+                assert!(
+                    message.contains("without parentheses"),
+                    "unexpected message for {}: {}",
+                    expr,
+                    message
+                );
+                // The error points at the offending operator
+                let range = err.location().unwrap().range();
+                assert_eq!(
+                    (u32::from(range.start()), u32::from(range.end())),
+                    location,
+                    "{}",
+                    expr
+                );
+            }
+            other => panic!("Expected Node error for {}, got {:?}", expr, other),
+        }
+    }
+}
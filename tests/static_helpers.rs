@@ -0,0 +1,159 @@
+use exprimo::EvaluationError;
+use serde_json::{json, Value};
+
+mod common;
+use common::evaluator_with;
+
+// Keys are written in sorted order, so the expectations hold with and without the
+// `preserve_order` feature
+
+#[test]
+fn test_object_keys_values_entries() {
+    let evaluator = evaluator_with(json!({
+        "tags": { "env": "prod", "team": "core" },
+        "empty": {}
+    }));
+
+    for (expr, expected) in [
+        ("Object.keys(tags)", json!(["env", "team"])),
+        ("Object.values(tags)", json!(["prod", "core"])),
+        (
+            "Object.entries(tags)",
+            json!([["env", "prod"], ["team", "core"]]),
+        ),
+        ("Object.keys(tags).length > 0", json!(true)),
+        ("Object.keys(empty).length > 0", json!(false)),
+        ("Object.keys(['a', 'b'])", json!(["0", "1"])),
+        ("Object.values('hi')", json!(["h", "i"])),
+        ("Object.keys(42)", json!([])),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+}
+
+#[test]
+fn test_object_from_entries_and_assign() {
+    let evaluator = evaluator_with(json!({
+        "defaults": { "currency": "EUR", "retries": 3 },
+        "overrides": { "retries": 5 }
+    }));
+
+    assert_eq!(
+        evaluator
+            .evaluate("Object.fromEntries([['a', 1], ['b', [2]], [3, null]])")
+            .unwrap(),
+        json!({ "a": 1, "b": [2], "3": null })
+    );
+    assert_eq!(
+        evaluator
+            .evaluate(
+                "Object.fromEntries(Object.entries(defaults).filter(e => e[0] !== 'retries'))"
+            )
+            .unwrap(),
+        json!({ "currency": "EUR" })
+    );
+    assert_eq!(
+        evaluator
+            .evaluate("Object.assign({}, defaults, overrides, null)")
+            .unwrap(),
+        json!({ "currency": "EUR", "retries": 5 })
+    );
+    // The context is never modified
+    assert_eq!(
+        evaluator
+            .evaluate("Object.assign(defaults, overrides).retries + defaults.retries")
+            .unwrap(),
+        json!(8)
+    );
+}
+
+#[test]
+fn test_array_statics() {
+    let evaluator = evaluator_with(json!({
+        "list": [1, 2],
+        "obj": { "length": 2 },
+        "arrayLike": { "0": "a", "1": "b", "length": 2 }
+    }));
+
+    for (expr, expected) in [
+        ("Array.isArray(list)", json!(true)),
+        ("Array.isArray(obj)", json!(false)),
+        ("Array.isArray('ab')", json!(false)),
+        ("Array.of(1, 'a', null)", json!([1, "a", null])),
+        ("Array.of()", json!([])),
+        ("Array.from(list)", json!([1, 2])),
+        ("Array.from(list, n => n * 10)", json!([10, 20])),
+        // Strings are split into code points
+        ("Array.from('a😀')", json!(["a", "😀"])),
+        ("Array.from(arrayLike)", json!(["a", "b"])),
+        ("Array.from(obj, (_, i) => i)", json!([0, 1])),
+        (
+            "Array.from({ length: 3 }, (x, i) => i * 2)",
+            json!([0, 2, 4]),
+        ),
+        ("Array.from(5)", json!([])),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+}
+
+#[test]
+fn test_static_helper_typeof_and_shadowing() {
+    let evaluator = evaluator_with(json!({}));
+    assert_eq!(
+        evaluator.evaluate("typeof Object").unwrap(),
+        json!("function")
+    );
+    assert_eq!(
+        evaluator.evaluate("typeof Array.isArray").unwrap(),
+        json!("function")
+    );
+    assert_eq!(evaluator.evaluate("Object.nope").unwrap(), Value::Null);
+
+    let evaluator = evaluator_with(json!({ "Object": { "keys": [1] } }));
+    assert_eq!(evaluator.evaluate("Object.keys").unwrap(), json!([1]));
+}
+
+#[test]
+fn test_static_helper_errors() {
+    let evaluator = evaluator_with(json!({ "missing": null }));
+
+    for expr in [
+        "Object.keys(missing)",
+        "Object.fromEntries([1])",
+        "Object.assign(1, {})",
+        "Array.from(missing)",
+        "Array.from([1], 2)",
+        "Array.from({ length: 1e12 })",
+    ] {
+        let result = evaluator.evaluate(expr);
+        match result {
+            Err(EvaluationError::TypeError(_, _)) => {}
+            _ => panic!("Expected TypeError for {}, got {:?}", expr, result),
+        }
+    }
+}
+
+#[cfg(not(feature = "preserve_order"))]
+#[test]
+fn test_object_keys_are_sorted_by_default() {
+    let evaluator = evaluator_with(json!({}));
+    assert_eq!(
+        evaluator
+            .evaluate("Object.keys({ b: 1, a: 2, c: 3 })")
+            .unwrap(),
+        json!(["a", "b", "c"])
+    );
+}
+
+#[cfg(feature = "preserve_order")]
+#[test]
+fn test_object_keys_keep_insertion_order() {
+    let evaluator = evaluator_with(json!({}));
+    assert_eq!(
+        evaluator
+            .evaluate("Object.keys({ b: 1, a: 2, c: 3 })")
+            .unwrap(),
+        json!(["b", "a", "c"])
+    );
+}
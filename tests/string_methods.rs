@@ -0,0 +1,230 @@
+use exprimo::{CustomFuncError, EvaluationError};
+use serde_json::{json, Value};
+
+mod common;
+use common::evaluator_with;
+
+#[test]
+fn test_string_length_and_indexing() {
+    let evaluator = evaluator_with(json!({ "name": "hello", "emoji": "😀a" }));
+
+    for (expr, expected) in [
+        ("name.length", json!(5)),
+        ("''.length", json!(0)),
+        // Astral characters count as two UTF-16 code units
+        ("emoji.length", json!(3)),
+        ("emoji.indexOf('a')", json!(2)),
+        ("emoji[2]", json!("a")),
+        ("name.charAt(1)", json!("e")),
+        ("name.charAt()", json!("h")),
+        ("name.charAt(10)", json!("")),
+        ("name.at(-1)", json!("o")),
+        ("name.at(5)", Value::Null),
+        ("name.at()", json!("h")),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+}
+
+#[test]
+fn test_string_search_methods() {
+    let evaluator = evaluator_with(json!({ "path": "/api/v1/users" }));
+
+    for (expr, expected) in [
+        ("path.startsWith('/api')", true),
+        ("path.startsWith('api', 1)", true),
+        ("path.startsWith('api')", false),
+        ("path.endsWith('users')", true),
+        ("path.endsWith('v1', 7)", true),
+        ("path.includes('/v1/')", true),
+        ("path.includes('/api', 1)", false),
+        ("path.includes('')", true),
+    ] {
+        assert_eq!(
+            evaluator.evaluate(expr).unwrap(),
+            Value::Bool(expected),
+            "{}",
+            expr
+        );
+    }
+
+    for (expr, expected) in [
+        ("path.indexOf('/')", 0.0),
+        ("path.indexOf('/', 1)", 4.0),
+        ("path.indexOf('x')", -1.0),
+        ("path.indexOf('', 100)", 13.0),
+    ] {
+        assert_eq!(
+            evaluator.evaluate(expr).unwrap().as_f64(),
+            Some(expected),
+            "{}",
+            expr
+        );
+    }
+}
+
+#[test]
+fn test_string_slice_and_substring() {
+    let evaluator = evaluator_with(json!({ "word": "exprimo" }));
+
+    for (expr, expected) in [
+        ("word.slice(1)", "xprimo"),
+        ("word.slice(1, 3)", "xp"),
+        ("word.slice(-3)", "imo"),
+        ("word.slice(3, 1)", ""),
+        ("word.substring(1, 3)", "xp"),
+        // `substring` swaps its bounds and treats negatives as 0
+        ("word.substring(3, 1)", "xp"),
+        ("word.substring(-3, 2)", "ex"),
+    ] {
+        assert_eq!(
+            evaluator.evaluate(expr).unwrap(),
+            json!(expected),
+            "{}",
+            expr
+        );
+    }
+}
+
+#[test]
+fn test_string_case_and_trim() {
+    let evaluator = evaluator_with(json!({ "padded": " \t Hello\u{a0}\n" }));
+
+    for (expr, expected) in [
+        ("padded.trim()", "Hello"),
+        ("padded.trimStart()", "Hello\u{a0}\n"),
+        ("padded.trimEnd()", " \t Hello"),
+        ("padded.trim().toUpperCase()", "HELLO"),
+        ("padded.trim().toLowerCase()", "hello"),
+        ("'Straße'.toUpperCase()", "STRASSE"),
+    ] {
+        assert_eq!(
+            evaluator.evaluate(expr).unwrap(),
+            json!(expected),
+            "{}",
+            expr
+        );
+    }
+}
+
+#[test]
+fn test_string_split() {
+    let evaluator = evaluator_with(json!({ "csv": "a,b,,c" }));
+
+    for (expr, expected) in [
+        ("csv.split(',')", json!(["a", "b", "", "c"])),
+        ("csv.split(',', 2)", json!(["a", "b"])),
+        ("csv.split(',', 0)", json!([])),
+        ("csv.split()", json!(["a,b,,c"])),
+        ("'abc'.split('')", json!(["a", "b", "c"])),
+        ("''.split('')", json!([])),
+        ("''.split(',')", json!([""])),
+        ("csv.split(',,')", json!(["a,b", "c"])),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+}
+
+#[test]
+fn test_string_replace() {
+    let evaluator = evaluator_with(json!({ "text": "a-b-c" }));
+
+    for (expr, expected) in [
+        ("text.replace('-', '+')", "a+b-c"),
+        ("text.replaceAll('-', '+')", "a+b+c"),
+        ("text.replace('x', '+')", "a-b-c"),
+        ("'ab'.replaceAll('', '.')", ".a.b."),
+        // Replacement patterns
+        ("text.replace('b', '[$&]')", "a-[b]-c"),
+        ("text.replace('b', '$`')", "a-a--c"),
+        ("text.replace('b', \"$'\")", "a--c-c"),
+        ("text.replace('b', '$$')", "a-$-c"),
+        ("text.replace('b', '$1')", "a-$1-c"),
+        // A function receives the match and its offset
+        ("text.replaceAll('-', (m, i) => i)", "a1b3c"),
+        ("text.replace('b', m => m.toUpperCase())", "a-B-c"),
+    ] {
+        assert_eq!(
+            evaluator.evaluate(expr).unwrap(),
+            json!(expected),
+            "{}",
+            expr
+        );
+    }
+}
+
+#[test]
+fn test_string_pad_and_repeat() {
+    let evaluator = evaluator_with(json!({ "id": "42" }));
+
+    for (expr, expected) in [
+        ("id.padStart(5, '0')", "00042"),
+        ("id.padEnd(5)", "42   "),
+        ("id.padStart(6, 'ab')", "abab42"),
+        ("id.padStart(1, '0')", "42"),
+        ("id.padStart(5, '')", "42"),
+        ("id.repeat(3)", "424242"),
+        ("id.repeat(0)", ""),
+    ] {
+        assert_eq!(
+            evaluator.evaluate(expr).unwrap(),
+            json!(expected),
+            "{}",
+            expr
+        );
+    }
+
+    for expr in ["id.repeat(-1)", "id.repeat(Infinity)", "id.repeat(1e10)"] {
+        let result = evaluator.evaluate(expr);
+        match result {
+            Err(EvaluationError::TypeError(_, _)) => {}
+            _ => panic!("Expected TypeError for {}, got {:?}", expr, result),
+        }
+    }
+}
+
+#[test]
+fn test_string_locale_compare() {
+    let evaluator = evaluator_with(json!({ "names": ["bob", "Alice", "alice", "Bob"] }));
+
+    assert_eq!(
+        evaluator.evaluate("'a'.localeCompare('b')").unwrap(),
+        json!(-1)
+    );
+    assert_eq!(
+        evaluator.evaluate("'b'.localeCompare('a')").unwrap(),
+        json!(1)
+    );
+    assert_eq!(
+        evaluator.evaluate("'a'.localeCompare('a')").unwrap(),
+        json!(0)
+    );
+    assert_eq!(
+        evaluator
+            .evaluate("names.toSorted((a, b) => a.localeCompare(b))")
+            .unwrap(),
+        json!(["alice", "Alice", "bob", "Bob"])
+    );
+}
+
+#[test]
+fn test_string_method_errors() {
+    let evaluator = evaluator_with(json!({ "name": "hello" }));
+
+    let result = evaluator.evaluate("name.startsWith()");
+    match result {
+        Err(EvaluationError::CustomFunction(CustomFuncError::ArityError { expected, got }, _)) => {
+            assert_eq!((expected, got), (1, 0));
+        }
+        _ => panic!("Expected ArityError, got {:?}", result),
+    }
+
+    // Unknown properties on strings are still errors
+    let result = evaluator.evaluate("name.foo");
+    match result {
+        Err(EvaluationError::TypeError(msg, _)) => {
+            assert!(msg.contains("trying to access property: foo"), "{}", msg)
+        }
+        _ => panic!("Expected TypeError, got {:?}", result),
+    }
+}
@@ -0,0 +1,150 @@
+use exprimo::{CustomFuncError, CustomFunction, EvaluationError, Evaluator};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+mod common;
+
+/// Uppercases every substituted value, leaving the literal parts alone.
+#[derive(Debug)]
+struct Shout;
+
+impl CustomFunction for Shout {
+    fn call(&self, args: &[Value]) -> Result<Value, CustomFuncError> {
+        let Some(Value::Array(strings)) = args.first() else {
+            return Err(CustomFuncError::ArgumentError(
+                "Expected template strings".to_string(),
+            ));
+        };
+        let mut result = String::new();
+        for (i, part) in strings.iter().enumerate() {
+            result.push_str(part.as_str().unwrap());
+            if let Some(value) = args.get(i + 1) {
+                let text = match value {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                result.push_str(&text.to_uppercase());
+            }
+        }
+        Ok(Value::String(result))
+    }
+}
+
+/// Returns its arguments unchanged so tests can inspect them.
+#[derive(Debug)]
+struct Args;
+
+impl CustomFunction for Args {
+    fn call(&self, args: &[Value]) -> Result<Value, CustomFuncError> {
+        Ok(Value::Array(args.to_vec()))
+    }
+}
+
+fn evaluator_with(context: Value) -> Evaluator {
+    let context = common::context(context);
+    let mut custom_functions: HashMap<String, Arc<dyn CustomFunction>> = HashMap::new();
+    custom_functions.insert("shout".to_string(), Arc::new(Shout));
+    custom_functions.insert("args".to_string(), Arc::new(Args));
+    Evaluator::new(context, custom_functions)
+}
+
+#[test]
+fn test_template_literal_interpolation() {
+    let evaluator = evaluator_with(json!({
+        "user": { "name": "Ada", "admin": true },
+        "count": 3
+    }));
+
+    assert_eq!(
+        evaluator.evaluate("`Hello ${user.name}!`").unwrap(),
+        json!("Hello Ada!")
+    );
+    assert_eq!(
+        evaluator
+            .evaluate("`${user.name} has ${count} items`")
+            .unwrap(),
+        json!("Ada has 3 items")
+    );
+    assert_eq!(
+        evaluator
+            .evaluate("`${user.admin ? 'admin' : 'user'}:${user.name}`")
+            .unwrap(),
+        json!("admin:Ada")
+    );
+    assert_eq!(evaluator.evaluate("`plain`").unwrap(), json!("plain"));
+    assert_eq!(evaluator.evaluate("``").unwrap(), json!(""));
+    assert_eq!(
+        evaluator.evaluate("`${user.name}${user.name}`").unwrap(),
+        json!("AdaAda")
+    );
+    assert_eq!(
+        evaluator
+            .evaluate("`outer ${`inner ${user.name}`}`")
+            .unwrap(),
+        json!("outer inner Ada")
+    );
+}
+
+#[test]
+fn test_template_literal_escapes() {
+    let evaluator = evaluator_with(json!({ "x": "y" }));
+
+    assert_eq!(
+        evaluator.evaluate(r"`a\nb\t${x}`").unwrap(),
+        json!("a\nb\ty")
+    );
+    assert_eq!(
+        evaluator.evaluate(r"`\${x} costs \`5\``").unwrap(),
+        json!("${x} costs `5`")
+    );
+}
+
+#[test]
+fn test_template_literal_missing_identifier() {
+    let evaluator = evaluator_with(json!({}));
+
+    let result = evaluator.evaluate("`Hi ${missing}`");
+    match result {
+        Err(EvaluationError::Node(_)) => {}
+        _ => panic!("Expected Node error, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_tagged_template() {
+    let evaluator = evaluator_with(json!({ "name": "ada", "n": 2 }));
+
+    assert_eq!(
+        evaluator.evaluate("shout`hi ${name}!`").unwrap(),
+        json!("hi ADA!")
+    );
+    // The tag receives the string parts followed by the values
+    assert_eq!(
+        evaluator.evaluate("args`a${name}b${n}`").unwrap(),
+        json!([["a", "b", ""], "ada", 2])
+    );
+    assert_eq!(
+        evaluator.evaluate("args`plain`").unwrap(),
+        json!([["plain"]])
+    );
+}
+
+#[test]
+fn test_tagged_template_unknown_function() {
+    let evaluator = evaluator_with(json!({ "name": "ada" }));
+
+    let result = evaluator.evaluate("nope`hi ${name}`");
+    match result {
+        Err(EvaluationError::Node(err)) => {
+            assert!(err.to_string().contains("Function 'nope' not found."));
+        }
+        _ => panic!("Expected Node error, got {:?}", result),
+    }
+
+    let result = evaluator.evaluate("name.length`hi`");
+    match result {
+        Err(EvaluationError::Node(_)) => {}
+        _ => panic!("Expected Node error, got {:?}", result),
+    }
+}
@@ -0,0 +1,301 @@
+use exprimo::{
+    CustomFuncError, CustomFunction, EvaluationError, Evaluator, NonJsonPolicy, Number, Value,
+};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+mod common;
+use common::evaluator_with;
+
+fn number(value: Value) -> f64 {
+    value.as_f64().expect("expected a number")
+}
+
+#[test]
+fn test_division_by_zero_and_overflow() {
+    let evaluator = evaluator_with(json!({ "big": 1e308 }));
+
+    assert_eq!(
+        number(evaluator.evaluate_value("5 / 0").unwrap()),
+        f64::INFINITY
+    );
+    assert_eq!(
+        number(evaluator.evaluate_value("-5 / 0").unwrap()),
+        f64::NEG_INFINITY
+    );
+    assert!(number(evaluator.evaluate_value("0 / 0").unwrap()).is_nan());
+    assert!(number(evaluator.evaluate_value("5 % 0").unwrap()).is_nan());
+    // Overflowing to infinity used to panic
+    assert_eq!(
+        number(evaluator.evaluate_value("big + big").unwrap()),
+        f64::INFINITY
+    );
+    assert_eq!(
+        number(evaluator.evaluate_value("big * -10").unwrap()),
+        f64::NEG_INFINITY
+    );
+
+    for (expr, expected) in [
+        ("5 / 0 === Infinity", json!(true)),
+        ("-5 / 0 === -Infinity", json!(true)),
+        ("0 / 0 === 0 / 0", json!(false)),
+        ("isNaN(0 / 0)", json!(true)),
+        ("[0 / 0].includes(NaN)", json!(true)),
+        ("1 / 0 > 1e308", json!(true)),
+        ("'' + 1 / 0", json!("Infinity")),
+        ("`${0 / 0}`", json!("NaN")),
+        ("String(-1 / 0)", json!("-Infinity")),
+        ("1 / 0 ? 'yes' : 'no'", json!("yes")),
+        ("0 / 0 ? 'yes' : 'no'", json!("no")),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+}
+
+#[test]
+fn test_negative_zero() {
+    let evaluator = evaluator_with(json!({}));
+
+    let zero = number(evaluator.evaluate_value("-0").unwrap());
+    assert!(zero == 0.0 && zero.is_sign_negative());
+    assert_eq!(
+        number(evaluator.evaluate_value("1 / -0").unwrap()),
+        f64::NEG_INFINITY
+    );
+    assert_eq!(
+        number(evaluator.evaluate_value("0 * -1").unwrap()).signum(),
+        -1.0
+    );
+
+    for (expr, expected) in [
+        ("-0 === 0", json!(true)),
+        ("String(-0)", json!("0")),
+        ("[-0].includes(0)", json!(true)),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+
+    // Negative zero survives the conversion to JSON
+    let result = evaluator.evaluate("-0").unwrap();
+    assert!(result.as_f64().unwrap().is_sign_negative());
+}
+
+#[test]
+fn test_undefined() {
+    let evaluator = evaluator_with(json!({
+        "order": { "coupon": null, "id": 7 },
+        "tags": ["a"]
+    }));
+
+    assert_eq!(
+        evaluator.evaluate_value("order.missing").unwrap(),
+        Value::Undefined
+    );
+    assert_eq!(
+        evaluator.evaluate_value("tags[5]").unwrap(),
+        Value::Undefined
+    );
+    assert_eq!(
+        evaluator.evaluate_value("void 0").unwrap(),
+        Value::Undefined
+    );
+    assert_eq!(
+        evaluator.evaluate_value("order.coupon").unwrap(),
+        Value::Null
+    );
+    assert_eq!(
+        evaluator.evaluate_value("order.coupon?.code").unwrap(),
+        Value::Undefined
+    );
+
+    for (expr, expected) in [
+        ("order.missing === undefined", json!(true)),
+        ("order.missing === null", json!(false)),
+        ("order.missing == null", json!(true)),
+        ("order.coupon === undefined", json!(false)),
+        ("order.coupon == undefined", json!(true)),
+        ("null == 0", json!(false)),
+        ("undefined == false", json!(false)),
+        ("typeof order.missing", json!("undefined")),
+        ("typeof order.coupon", json!("object")),
+        ("order.missing ?? 'none'", json!("none")),
+        ("order.coupon ?? 'none'", json!("none")),
+        ("'x' + order.missing", json!("xundefined")),
+        ("String(undefined)", json!("undefined")),
+        ("isNaN(order.missing + 1)", json!(true)),
+        ("order.coupon + 1", json!(1)),
+        ("[null, undefined, 1].join('-')", json!("--1")),
+        (
+            "[1, , 3].map(x => x === undefined)",
+            json!([false, true, false]),
+        ),
+        ("tags.find(t => t === 'z') === undefined", json!(true)),
+        // Missing arguments are undefined
+        (
+            "tags.map((t, i, all, extra) => extra === undefined)",
+            json!([true]),
+        ),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+}
+
+#[test]
+fn test_string_comparison_and_concatenation() {
+    let evaluator = evaluator_with(json!({ "tags": ["a", "b"], "meta": { "id": 1 } }));
+
+    for (expr, expected) in [
+        // Two strings compare by code units, not as numbers
+        ("'b' > 'a'", json!(true)),
+        ("'10' > '9'", json!(false)),
+        ("'10' < '9'", json!(true)),
+        ("'abc' < 'abd'", json!(true)),
+        ("'a' < 'ab'", json!(true)),
+        ("'B' < 'a'", json!(true)),
+        ("'a' >= 'a'", json!(true)),
+        // Astral characters sort by their UTF-16 surrogates
+        ("'\u{1F600}' < '\u{FF61}'", json!(true)),
+        // A number on either side compares numerically
+        ("'10' > 9", json!(true)),
+        ("10 > '9'", json!(true)),
+        ("'abc' < 1", json!(false)),
+        ("[2] > 1", json!(true)),
+        ("[10] < ['9']", json!(true)),
+        // Arrays and objects are joined as by `String(x)`
+        ("[] + {}", json!("[object Object]")),
+        ("[1, 2] + ''", json!("1,2")),
+        ("'tags: ' + tags", json!("tags: a,b")),
+        ("meta + '!'", json!("[object Object]!")),
+        ("[1] + [2, [3]]", json!("12,3")),
+        ("`${tags}`", json!("a,b")),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+}
+
+#[test]
+fn test_non_json_policy() {
+    let expressions = [
+        "0 / 0",
+        "[1 / 0, undefined]",
+        "({ a: undefined, b: -1 / 0 })",
+    ];
+
+    let evaluator = Evaluator::default();
+    let results: Vec<_> = expressions
+        .iter()
+        .map(|expr| evaluator.evaluate(expr).unwrap())
+        .collect();
+    assert_eq!(
+        results,
+        vec![json!(null), json!([null, null]), json!({ "b": null })]
+    );
+
+    let evaluator = Evaluator::default().with_non_json_policy(NonJsonPolicy::String);
+    let results: Vec<_> = expressions
+        .iter()
+        .map(|expr| evaluator.evaluate(expr).unwrap())
+        .collect();
+    assert_eq!(
+        results,
+        vec![
+            json!("NaN"),
+            json!(["Infinity", null]),
+            json!({ "b": "-Infinity" })
+        ]
+    );
+
+    let evaluator = Evaluator::default().with_non_json_policy(NonJsonPolicy::Error);
+    for (expr, value) in expressions.iter().zip(["NaN", "Infinity", "undefined"]) {
+        match evaluator.evaluate(expr) {
+            Err(EvaluationError::NonJsonValue(found, _)) => assert_eq!(found, value, "{}", expr),
+            result => panic!("Expected NonJsonValue for {}, got {:?}", expr, result),
+        }
+    }
+    // The native result is always available
+    assert!(evaluator
+        .evaluate_value("0 / 0")
+        .unwrap()
+        .as_f64()
+        .unwrap()
+        .is_nan());
+    assert_eq!(evaluator.evaluate("1 / 4").unwrap(), json!(0.25));
+}
+
+#[test]
+fn test_json_stringify_non_json_values() {
+    let evaluator = evaluator_with(json!({}));
+
+    for (expr, expected) in [
+        (
+            "JSON.stringify({ a: undefined, b: 0 / 0, c: 1 })",
+            json!(r#"{"b":null,"c":1}"#),
+        ),
+        ("JSON.stringify([undefined, 1 / 0])", json!("[null,null]")),
+        ("typeof JSON.stringify(undefined)", json!("undefined")),
+        ("JSON.stringify(-0)", json!("0")),
+    ] {
+        assert_eq!(evaluator.evaluate(expr).unwrap(), expected, "{}", expr);
+    }
+}
+
+#[test]
+fn test_lossless_json_conversion() {
+    let context = json!({
+        "big": 9007199254740993u64,
+        "float": 1.5,
+        "max": u64::MAX,
+        "min": i64::MIN,
+        "nested": { "items": [1, -2, 3.25, null, true, "x"] },
+        "whole": 12345.0
+    });
+    let evaluator = evaluator_with(context.clone());
+
+    // Context values come back exactly as they went in, including integers beyond 2^53
+    for (name, value) in context.as_object().unwrap() {
+        assert_eq!(&evaluator.evaluate(name).unwrap(), value, "{}", name);
+        assert_eq!(
+            Value::from(value).to_json(NonJsonPolicy::Error).unwrap(),
+            *value
+        );
+    }
+    assert!(evaluator.evaluate("whole").unwrap().is_f64());
+    assert!(evaluator.evaluate("big").unwrap().is_u64());
+
+    let big = evaluator.evaluate_value("big").unwrap();
+    match big {
+        Value::Number(n) => assert_eq!(n.as_u64(), Some(9007199254740993)),
+        other => panic!("Expected a number, got {:?}", other),
+    }
+    assert_eq!(Number::from(-3i64).as_i64(), Some(-3));
+    assert_eq!(Number::from(2u64), Number::from_f64(2.0));
+    assert_ne!(Number::from_f64(f64::NAN), Number::from_f64(f64::NAN));
+}
+
+#[derive(Debug)]
+struct Describe;
+
+impl CustomFunction for Describe {
+    fn call(&self, args: &[serde_json::Value]) -> Result<serde_json::Value, CustomFuncError> {
+        Ok(json!(format!("{:?}", args)))
+    }
+}
+
+#[test]
+fn test_custom_function_arguments_follow_policy() {
+    let mut custom_functions: HashMap<String, Arc<dyn CustomFunction>> = HashMap::new();
+    custom_functions.insert("describe".to_string(), Arc::new(Describe));
+    let evaluator = Evaluator::with_functions(custom_functions.clone());
+    assert_eq!(
+        evaluator.evaluate("describe(0 / 0, undefined)").unwrap(),
+        json!("[Null, Null]")
+    );
+
+    let evaluator =
+        Evaluator::with_functions(custom_functions).with_non_json_policy(NonJsonPolicy::Error);
+    assert!(matches!(
+        evaluator.evaluate("describe(0 / 0)"),
+        Err(EvaluationError::NonJsonValue(_, _))
+    ));
+}